@@ -7,18 +7,20 @@
 //! traditional formats.
 
 use std::fs::File;
-use std::io::{BufWriter, Read, Write};
+use std::io::{self, BufWriter, Read, Write};
 use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use flate2::write::GzEncoder;
 use flate2::Compression as GzCompression;
 
-use ancf_codecs::{Lz4Codec, ZstdCodec};
-use ancf_core::format::DEFAULT_BLOCK_SIZE;
+use ancf_codecs::{Fsst, Lz4Codec, PassThroughCodec, ZstdCodec};
+use ancf_core::codec::BlockMeta;
+use ancf_core::format::{BLOCK_ENTRY_SIZE, DEFAULT_BLOCK_SIZE, HEADER_SIZE};
 use ancf_core::{Codec, Reader, Writer};
+use xxhash_rust::xxh3::xxh3_64;
 
 // ── constants ──────────────────────────────────────────────────────────────
 
@@ -164,6 +166,173 @@ fn write_raw_gzip(path: &Path) -> Result<u64> {
     Ok(total)
 }
 
+/// Train an FSST symbol table once over a sample of the corpus.
+///
+/// Returns the trained table alongside the number of symbols it learned so the
+/// comparison can report how the single shared table performs over the whole
+/// input, mirroring `raw zstd`'s single-stream measurement.
+fn train_fsst() -> (Fsst, usize) {
+    // Sample enough lines to cover the synthetic corpus's vocabulary without
+    // walking all 100 MB — the table is static, so a representative slice is
+    // enough to learn the recurring IPs, paths, and user-agent strings.
+    const SAMPLE_LINES: u64 = 50_000;
+    let sample: Vec<Vec<u8>> = (0..SAMPLE_LINES).map(generate_log_line).collect();
+    let refs: Vec<&[u8]> = sample.iter().map(|l| l.as_slice()).collect();
+    let table = Fsst::train_bulk(&refs);
+    let count = table.symbol_count();
+    (table, count)
+}
+
+/// Compress the corpus line-by-line with a trained FSST table.
+///
+/// When `wrap_zstd` is set the FSST code stream is piped through zstd, showing
+/// the `fsst_zstd` cascade — symbol substitution first, entropy coding second.
+fn write_fsst(path: &Path, table: &Fsst, wrap_zstd: bool) -> Result<u64> {
+    let file = File::create(path)?;
+    let mut sink: Box<dyn Write> = if wrap_zstd {
+        Box::new(zstd::stream::write::Encoder::new(BufWriter::new(file), 3)?.auto_finish())
+    } else {
+        Box::new(BufWriter::new(file))
+    };
+    let mut total = 0u64;
+    let mut i = 0u64;
+    while total < TARGET_RAW_BYTES {
+        let line = generate_log_line(i);
+        total += line.len() as u64;
+        sink.write_all(&table.compress(&line))?;
+        i += 1;
+    }
+    sink.flush()?;
+    Ok(total)
+}
+
+// ── content-defined chunking (dedup algotest) ───────────────────────────────
+
+/// FastCDC chunk-size bounds (2 / 8 / 64 KiB).
+const CDC_MIN: usize = 2 * 1024;
+const CDC_AVG: usize = 8 * 1024;
+const CDC_MAX: usize = 64 * 1024;
+
+/// Normalized-chunking masks from the FastCDC paper, tuned for an 8 KiB target.
+/// `MASK_S` has more set bits (rarer cuts) and applies before the average size
+/// to suppress tiny chunks; `MASK_L` has fewer (more frequent cuts) and applies
+/// after it to cap large chunks — together they tighten the size distribution.
+const CDC_MASK_S: u64 = 0x0003_5907_0353_0000;
+const CDC_MASK_L: u64 = 0x0000_d900_0353_0000;
+
+/// Deterministic Gear table for the rolling hash (an LCG fill keeps the demo
+/// reproducible without pulling in a random dependency).
+fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    for slot in table.iter_mut() {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        *slot = state;
+    }
+    table
+}
+
+/// Split `data` into content-defined chunks using a Gear-based rolling hash
+/// with normalized chunking and hard 2/8/64 KiB bounds. Returns each chunk's
+/// length.
+fn fastcdc_chunk(data: &[u8], gear: &[u64; 256]) -> Vec<usize> {
+    let n = data.len();
+    let mut offset = 0;
+    let mut sizes = Vec::new();
+    while offset < n {
+        let remaining = n - offset;
+        if remaining <= CDC_MIN {
+            sizes.push(remaining);
+            break;
+        }
+        let end = (offset + CDC_MAX).min(n);
+        let normal = (offset + CDC_AVG).min(end);
+        let mut hash = 0u64;
+        let mut pos = offset;
+        let mut boundary = end;
+        while pos < end {
+            hash = (hash << 1).wrapping_add(gear[data[pos] as usize]);
+            pos += 1;
+            if pos < offset + CDC_MIN {
+                continue; // enforce the minimum chunk size
+            }
+            let mask = if pos < normal { CDC_MASK_S } else { CDC_MASK_L };
+            if hash & mask == 0 {
+                boundary = pos;
+                break;
+            }
+        }
+        sizes.push(boundary - offset);
+        offset = boundary;
+    }
+    sizes
+}
+
+/// Measure deduplication savings from content-defined chunking over the corpus
+/// and print a `chunking` section with the dedup ratio and a size histogram.
+fn chunking_report() -> Result<()> {
+    section("1b · CONTENT-DEFINED CHUNKING (dedup)");
+
+    // Materialize the corpus once so we can slide the rolling hash across it.
+    let mut corpus = Vec::with_capacity(TARGET_RAW_BYTES as usize);
+    let mut i = 0u64;
+    while (corpus.len() as u64) < TARGET_RAW_BYTES {
+        corpus.extend_from_slice(&generate_log_line(i));
+        i += 1;
+    }
+
+    let gear = gear_table();
+    let mut offset = 0;
+    let mut seen: std::collections::HashMap<u64, ()> = std::collections::HashMap::new();
+    let mut total_chunks = 0u64;
+    let mut unique_chunks = 0u64;
+    let mut total_bytes = 0u64;
+    let mut unique_bytes = 0u64;
+    // Histogram buckets: <2K, 2–4K, 4–8K, 8–16K, 16–32K, 32–64K.
+    let mut hist = [0u64; 6];
+
+    for len in fastcdc_chunk(&corpus, &gear) {
+        let chunk = &corpus[offset..offset + len];
+        offset += len;
+        let h = xxh3_64(chunk);
+        total_chunks += 1;
+        total_bytes += len as u64;
+        if seen.insert(h, ()).is_none() {
+            unique_chunks += 1;
+            unique_bytes += len as u64;
+        }
+        let bucket = match len {
+            0..=2047 => 0,
+            2048..=4095 => 1,
+            4096..=8191 => 2,
+            8192..=16383 => 3,
+            16384..=32767 => 4,
+            _ => 5,
+        };
+        hist[bucket] += 1;
+    }
+
+    let dedup_ratio = total_bytes as f64 / unique_bytes.max(1) as f64;
+    let avg_size = total_bytes / total_chunks.max(1);
+    println!("  {:<28} {}", "Chunks (total / unique):",
+             format!("{} / {}", format_number(total_chunks), format_number(unique_chunks)));
+    println!("  {:<28} {}", "Bytes after dedup:",
+             format!("{} → {}", human_bytes(total_bytes), human_bytes(unique_bytes)));
+    println!("  {:<28} {:.2}×", "Dedup ratio:", dedup_ratio);
+    println!("  {:<28} {}", "Average chunk size:", human_bytes(avg_size));
+    println!();
+
+    let labels = ["  <2 KiB", " 2–4 KiB", " 4–8 KiB", "8–16 KiB", "16–32 KiB", "32–64 KiB"];
+    let max = hist.iter().copied().max().unwrap_or(1).max(1);
+    println!("  Chunk-size distribution:");
+    for (label, &count) in labels.iter().zip(hist.iter()) {
+        let bar = (count as usize * 40 / max as usize).max(if count > 0 { 1 } else { 0 });
+        println!("  {label} │{:<40}│ {}", "█".repeat(bar), format_number(count));
+    }
+    println!();
+    Ok(())
+}
+
 // ── traditional random-access simulation ────────────────────────────────────
 
 /// Simulate reading `target_raw_offset` bytes into a raw zstd stream.
@@ -277,7 +446,7 @@ fn run() -> Result<()> {
     print_compression_row("ANCF/zstd  (block=64KB)", raw_bytes, &ancf_zstd_path, "", false)?;
 
     timed_step("ANCF/lz4", || {
-        write_ancf(&ancf_lz4_path, Box::new(Lz4Codec), DEFAULT_BLOCK_SIZE)
+        write_ancf(&ancf_lz4_path, Box::new(Lz4Codec::default()), DEFAULT_BLOCK_SIZE)
     })?;
     print_compression_row("ANCF/lz4   (block=64KB)", raw_bytes, &ancf_lz4_path, "", false)?;
 
@@ -289,16 +458,32 @@ fn run() -> Result<()> {
     print_compression_row("raw gzip", raw_bytes, &raw_gzip_path,
         "← no random access", true)?;
 
+    // FSST: a single symbol table trained once over the corpus, then used to
+    // substitute short repetitive records before (optionally) entropy coding.
+    let fsst_path      = out_dir.join("corpus.fsst");
+    let fsst_zstd_path = out_dir.join("corpus.fsst.zst");
+    let (fsst_table, fsst_symbols) = train_fsst();
+    timed_step("fsst", || write_fsst(&fsst_path, &fsst_table, false))?;
+    print_compression_row("fsst", raw_bytes, &fsst_path,
+        &format!("← {fsst_symbols} symbols"), false)?;
+    timed_step("fsst+zstd", || write_fsst(&fsst_zstd_path, &fsst_table, true))?;
+    print_compression_row("fsst → zstd", raw_bytes, &fsst_zstd_path,
+        "← symbol table + entropy", false)?;
+
     println!();
     println!("  Total log entries : {}", format_number(lines));
     println!("  Raw data size     : {}", human_bytes(raw_bytes));
+    println!();
+
+    // ── Phase 1b: Content-defined chunking ────────────────────────────────────
+    chunking_report()?;
 
     // ── Phase 2: Inspection ──────────────────────────────────────────────────
     section("2 · ANCF FILE INSPECTION");
     let codec = Arc::new(ZstdCodec::default());
     let reader = Reader::open(&ancf_zstd_path, codec.clone())?;
     let block_count = reader.block_count();
-    let index_bytes = block_count * 32 + 8 + 56; // entries + footer + header
+    let index_bytes = block_count * BLOCK_ENTRY_SIZE + 8 + HEADER_SIZE; // entries + footer + header
     let index_pct   = index_bytes as f64 / std::fs::metadata(&ancf_zstd_path)?.len() as f64 * 100.0;
 
     println!("  block count    : {}", format_number(block_count));
@@ -350,7 +535,7 @@ fn run() -> Result<()> {
 
     // ANCF read_block
     let (ancf_dur, ancf_byte) = {
-        let mut r = Reader::open(&ancf_zstd_path, Arc::new(ZstdCodec::default()))?;
+        let r = Reader::open(&ancf_zstd_path, Arc::new(ZstdCodec::default()))?;
         let t0 = Instant::now();
         let block = r.read_block(target_block)?;
         let dur = t0.elapsed();
@@ -448,7 +633,7 @@ fn run() -> Result<()> {
     section("4 · SEQUENTIAL SCAN THROUGHPUT");
 
     let (scan_dur, scan_bytes) = {
-        let mut r = Reader::open(&ancf_zstd_path, Arc::new(ZstdCodec::default()))?;
+        let r = Reader::open(&ancf_zstd_path, Arc::new(ZstdCodec::default()))?;
         let t0 = Instant::now();
         let mut total = 0u64;
         for idx in 0..r.block_count() {
@@ -464,7 +649,7 @@ fn run() -> Result<()> {
     section("5 · RANDOM ACCESS BENCHMARK  —  1 000 random block reads");
 
     let mut latencies_us: Vec<u64> = {
-        let mut r = Reader::open(&ancf_zstd_path, Arc::new(ZstdCodec::default()))?;
+        let r = Reader::open(&ancf_zstd_path, Arc::new(ZstdCodec::default()))?;
         let bc = r.block_count();
         let mut rng = 0xDEAD_BEEF_CAFE_BABEu64;
         let indices: Vec<u64> = (0..1000)
@@ -548,7 +733,8 @@ fn run() -> Result<()> {
     println!();
 
     // cleanup temp files
-    for p in [&ancf_zstd_path, &ancf_lz4_path, &raw_zstd_path, &raw_gzip_path, &entropy_path] {
+    for p in [&ancf_zstd_path, &ancf_lz4_path, &raw_zstd_path, &raw_gzip_path,
+              &fsst_path, &fsst_zstd_path, &entropy_path] {
         let _ = std::fs::remove_file(p);
     }
 
@@ -598,9 +784,271 @@ fn timed_step<T, F: FnOnce() -> Result<T>>(label: &str, f: F) -> Result<T> {
     Ok(r)
 }
 
+// ── self-describing container ────────────────────────────────────────────────
+
+/// Container magic: lets `decode` reject foreign files before trusting a tag.
+const CONTAINER_MAGIC: &[u8; 4] = b"SQSH";
+
+/// Container format version, bumped on any on-disk layout change.
+const CONTAINER_VERSION: u8 = 1;
+
+/// A compression method, identified in the container by a one-byte tag so the
+/// decoder picks the right path from the header alone rather than the caller
+/// guessing from the file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    Store,
+    Zstd,
+    Lz4,
+    Gzip,
+    Fsst,
+}
+
+impl Method {
+    fn tag(self) -> u8 {
+        match self {
+            Method::Store => 0,
+            Method::Zstd => 1,
+            Method::Lz4 => 2,
+            Method::Gzip => 3,
+            Method::Fsst => 4,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self> {
+        Ok(match tag {
+            0 => Method::Store,
+            1 => Method::Zstd,
+            2 => Method::Lz4,
+            3 => Method::Gzip,
+            4 => Method::Fsst,
+            other => anyhow::bail!("unknown container method tag {other}"),
+        })
+    }
+
+    fn from_name(name: &str) -> Result<Self> {
+        Ok(match name {
+            "store" | "entropy" | "raw" => Method::Store,
+            "zstd" => Method::Zstd,
+            "lz4" => Method::Lz4,
+            "gzip" | "gz" => Method::Gzip,
+            "fsst" => Method::Fsst,
+            other => anyhow::bail!(
+                "unknown method '{other}' (expected store|zstd|lz4|gzip|fsst)"
+            ),
+        })
+    }
+}
+
+/// Compress `input` with `method`, returning the method-specific payload.
+fn encode_payload(method: Method, input: &[u8]) -> Result<Vec<u8>> {
+    Ok(match method {
+        Method::Store => PassThroughCodec.compress_block(input, &mut BlockMeta::default())?,
+        Method::Zstd => ZstdCodec::default().compress_block(input, &mut BlockMeta::default())?,
+        Method::Lz4 => Lz4Codec::default().compress_block(input, &mut BlockMeta::default())?,
+        Method::Gzip => {
+            let mut enc = GzEncoder::new(Vec::new(), GzCompression::default());
+            enc.write_all(input)?;
+            enc.finish()?
+        }
+        Method::Fsst => {
+            // FSST needs its symbol table to decode, so train over the input's
+            // lines and prefix the serialized table to the code stream.
+            let lines: Vec<&[u8]> = input.split_inclusive(|&b| b == b'\n').collect();
+            let table = Fsst::train_bulk(&lines);
+            let mut payload = table.serialize();
+            payload.extend_from_slice(&table.compress(input));
+            payload
+        }
+    })
+}
+
+/// Reverse [`encode_payload`], reconstructing the original bytes. `raw_len` is
+/// the original length from the container header, used by codecs that decode
+/// into a known-size buffer.
+fn decode_payload(method: Method, payload: &[u8], raw_len: usize) -> Result<Vec<u8>> {
+    let meta = BlockMeta::default();
+    Ok(match method {
+        Method::Store => PassThroughCodec.decompress_block(payload, &meta, raw_len)?,
+        Method::Zstd => ZstdCodec::default().decompress_block(payload, &meta, raw_len)?,
+        Method::Lz4 => Lz4Codec::default().decompress_block(payload, &meta, raw_len)?,
+        Method::Gzip => {
+            use flate2::read::GzDecoder;
+            let mut out = Vec::new();
+            GzDecoder::new(payload).read_to_end(&mut out)?;
+            out
+        }
+        Method::Fsst => {
+            let (table, consumed) = Fsst::deserialize(payload)?;
+            table.decompress(&payload[consumed..])
+        }
+    })
+}
+
+/// Write a self-describing container: magic, version, method tag, original
+/// length, an xxh3 checksum of the original bytes, then the payload.
+fn write_container(path: &Path, method: Method, input: &[u8]) -> Result<()> {
+    let payload = encode_payload(method, input)?;
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(CONTAINER_MAGIC)?;
+    out.write_all(&[CONTAINER_VERSION, method.tag()])?;
+    out.write_all(&(input.len() as u64).to_le_bytes())?;
+    out.write_all(&xxh3_64(input).to_le_bytes())?;
+    out.write_all(&payload)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Parse a container, dispatch to the decoder named in its header, and verify
+/// the decoded length and checksum against the header.
+fn read_container(path: &Path) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    if bytes.len() < 22 || &bytes[0..4] != CONTAINER_MAGIC {
+        anyhow::bail!("not a squish container (bad magic)");
+    }
+    let version = bytes[4];
+    if version != CONTAINER_VERSION {
+        anyhow::bail!("unsupported container version {version}");
+    }
+    let method = Method::from_tag(bytes[5])?;
+    let orig_len = u64::from_le_bytes(bytes[6..14].try_into().unwrap());
+    let checksum = u64::from_le_bytes(bytes[14..22].try_into().unwrap());
+
+    let decoded = decode_payload(method, &bytes[22..], orig_len as usize)?;
+    if decoded.len() as u64 != orig_len {
+        anyhow::bail!("length mismatch: header {orig_len}, decoded {}", decoded.len());
+    }
+    if xxh3_64(&decoded) != checksum {
+        anyhow::bail!("checksum mismatch: container is corrupt");
+    }
+    Ok(decoded)
+}
+
+/// Read a file argument, treating "-" as stdin.
+fn read_input(path: &str) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    if path == "-" {
+        io::stdin().read_to_end(&mut buf)?;
+    } else {
+        File::open(path)?.read_to_end(&mut buf)?;
+    }
+    Ok(buf)
+}
+
 fn main() {
-    if let Err(e) = run() {
+    let result = match std::env::args().nth(1).as_deref() {
+        Some("compress") => cmd_compress(),
+        Some("decompress") => cmd_decompress(),
+        Some("stream") => cmd_stream(),
+        _ => run(),
+    };
+    if let Err(e) = result {
         eprintln!("error: {e:#}");
         std::process::exit(1);
     }
 }
+
+/// `demo compress <method> <input> <output>` — write a self-describing container.
+fn cmd_compress() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(2).collect();
+    let [method, input, output] = args.as_slice() else {
+        anyhow::bail!("usage: demo compress <method> <input> <output>");
+    };
+    let bytes = read_input(input)?;
+    write_container(Path::new(output), Method::from_name(method)?, &bytes)?;
+    Ok(())
+}
+
+/// Bounded buffer size for the streaming pipeline (64 KiB), so arbitrarily
+/// large inputs flow through without becoming fully resident in memory.
+const STREAM_BUF: usize = 64 * 1024;
+
+/// Copy `reader` into `writer` through a fixed-size buffer.
+fn copy_bounded(mut reader: impl Read, mut writer: impl Write) -> Result<()> {
+    let mut buf = vec![0u8; STREAM_BUF];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        writer.write_all(&buf[..n])?;
+    }
+    Ok(())
+}
+
+/// `demo stream --codec <name> [--decompress]` — compress or decompress a
+/// shell pipeline between stdin and stdout.
+///
+/// stdin isn't seekable and carries no header, so the codec and direction are
+/// taken from explicit flags rather than sniffed. Everything flows through
+/// bounded buffers ([`STREAM_BUF`]) so large inputs never fully reside in RAM.
+fn cmd_stream() -> Result<()> {
+    let mut codec: Option<String> = None;
+    let mut decompress = false;
+    let mut args = std::env::args().skip(2);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--decompress" | "-d" => decompress = true,
+            "--codec" | "-c" => {
+                codec = Some(args.next().context("--codec requires a value")?);
+            }
+            other => anyhow::bail!("unexpected argument '{other}'"),
+        }
+    }
+    let method = Method::from_name(codec.as_deref().context("--codec is required")?)?;
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let input = stdin.lock();
+    let mut output = stdout.lock();
+
+    if decompress {
+        match method {
+            Method::Store => copy_bounded(input, &mut output)?,
+            Method::Zstd => copy_bounded(zstd::stream::read::Decoder::new(input)?, &mut output)?,
+            Method::Gzip => {
+                copy_bounded(flate2::read::GzDecoder::new(input), &mut output)?
+            }
+            Method::Lz4 | Method::Fsst => anyhow::bail!(
+                "codec {:?} has no streaming mode; use the container compress/decompress path",
+                method
+            ),
+        }
+    } else {
+        match method {
+            Method::Store => copy_bounded(input, &mut output)?,
+            Method::Zstd => {
+                let mut enc = zstd::stream::write::Encoder::new(&mut output, 3)?;
+                copy_bounded(input, &mut enc)?;
+                enc.finish()?;
+            }
+            Method::Gzip => {
+                let mut enc = GzEncoder::new(&mut output, GzCompression::default());
+                copy_bounded(input, &mut enc)?;
+                enc.finish()?;
+            }
+            Method::Lz4 | Method::Fsst => anyhow::bail!(
+                "codec {:?} has no streaming mode; use the container compress/decompress path",
+                method
+            ),
+        }
+    }
+    output.flush()?;
+    Ok(())
+}
+
+/// `demo decompress <input> <output>` — decoder chosen from the header alone.
+fn cmd_decompress() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(2).collect();
+    let [input, output] = args.as_slice() else {
+        anyhow::bail!("usage: demo decompress <input> <output>");
+    };
+    let decoded = read_container(Path::new(input))?;
+    if output == "-" {
+        io::stdout().write_all(&decoded)?;
+    } else {
+        File::create(output)?.write_all(&decoded)?;
+    }
+    Ok(())
+}
@@ -6,7 +6,10 @@ use std::time::Instant;
 use anyhow::Context;
 use clap::{Parser, Subcommand};
 
-use ancf_codecs::{codec_by_id, Lz4Codec, PassThroughCodec, ZstdCodec};
+use ancf_codecs::{
+    codec_by_id, AutoCodec, Bzip2Codec, DeltaIntCodec, IntCodec, Lz4Codec, PassThroughCodec, SnapCodec,
+    XzCodec, ZstdCodec,
+};
 use ancf_core::format::DEFAULT_BLOCK_SIZE;
 use ancf_core::{Codec, Reader, Writer};
 
@@ -40,6 +43,22 @@ enum Commands {
         /// Raw bytes per block (default: 65536 = 64 KB)
         #[arg(short, long, default_value_t = DEFAULT_BLOCK_SIZE)]
         block_size: u32,
+        /// Number of compression worker threads (1 = serial). Blocks are
+        /// independent, so this fans compression across cores while keeping the
+        /// on-disk layout identical to the serial path.
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+        /// Train a shared codec dictionary over the corpus and compress every
+        /// block against it. Recovers cross-block redundancy lost to small
+        /// independent blocks. zstd and lz4 train a dictionary; with other
+        /// codecs the file is written without one.
+        #[arg(long)]
+        train_dict: bool,
+        /// With --train-dict, train over only the first N blocks and stream the
+        /// rest against the dictionary (0 = sample the whole corpus). Bounds
+        /// writer memory to roughly N × block_size on large inputs.
+        #[arg(long, default_value_t = 0)]
+        dict_sample_blocks: usize,
     },
     /// Fully decompress an ANCF1 file back to raw bytes
     Decompress {
@@ -47,6 +66,10 @@ enum Commands {
         input: PathBuf,
         /// Destination file ("-" writes to stdout)
         output: PathBuf,
+        /// Abort on any checksum mismatch (verifies the raw-bytes checksum of
+        /// each decoded block, not just the compressed payload).
+        #[arg(long)]
+        strict: bool,
     },
     /// Print header metadata and block index statistics
     Inspect {
@@ -69,6 +92,21 @@ enum Commands {
         /// Write raw bytes to a file instead of printing a hex dump
         #[arg(short, long)]
         output: Option<PathBuf>,
+        /// Abort on any checksum mismatch (verifies the decoded raw bytes, not
+        /// just the compressed payload).
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Check every block's checksum and the index/footer consistency
+    Verify {
+        /// ANCF1 file to verify
+        file: PathBuf,
+    },
+    /// Recover the intact prefix of a damaged file by truncating to the last
+    /// good block and rebuilding the header, index, and footer
+    Repair {
+        /// ANCF1 file to repair (modified in place)
+        file: PathBuf,
     },
     /// Benchmark random-access reads across N randomly chosen blocks
     Bench {
@@ -80,6 +118,10 @@ enum Commands {
         /// Fixed random seed for reproducibility
         #[arg(long, default_value_t = 42)]
         seed: u64,
+        /// Decoded-block LRU cache budget in MB (0 = disabled). A warm cache
+        /// lets repeated indices skip disk I/O and decode entirely.
+        #[arg(long, default_value_t = 0)]
+        cache_mb: u64,
     },
 }
 
@@ -89,9 +131,15 @@ fn codec_from_name(name: &str, zstd_level: i32) -> anyhow::Result<Box<dyn Codec>
     match name {
         "passthrough" | "pass" | "none" => Ok(Box::new(PassThroughCodec)),
         "zstd" | "z" => Ok(Box::new(ZstdCodec::new(zstd_level))),
-        "lz4" | "l" => Ok(Box::new(Lz4Codec)),
+        "lz4" | "l" => Ok(Box::new(Lz4Codec::default())),
+        "delta-int" | "delta" | "d" => Ok(Box::new(DeltaIntCodec::default())),
+        "int" | "i" => Ok(Box::new(IntCodec::default())),
+        "snappy" | "snap" | "s" => Ok(Box::new(SnapCodec)),
+        "auto" | "a" => Ok(Box::new(AutoCodec::new(Box::new(ZstdCodec::new(zstd_level))))),
+        "xz" | "lzma" => Ok(Box::new(XzCodec::default())),
+        "bzip2" | "bz2" => Ok(Box::new(Bzip2Codec::default())),
         other => anyhow::bail!(
-            "unknown codec '{}'. Valid options: passthrough, zstd, lz4",
+            "unknown codec '{}'. Valid options: passthrough, zstd, lz4, delta-int, int, snappy, auto, xz, bzip2",
             other
         ),
     }
@@ -120,12 +168,31 @@ fn run_compress(
     codec_name: &str,
     zstd_level: i32,
     block_size: u32,
+    jobs: usize,
+    train_dict: bool,
+    dict_sample_blocks: usize,
 ) -> anyhow::Result<()> {
     let codec = codec_from_name(codec_name, zstd_level)?;
     let codec_display = codec.name().to_string();
 
-    let mut writer = Writer::create(&output, codec, block_size)
-        .with_context(|| format!("creating output file {:?}", output))?;
+    let mut writer = if train_dict {
+        // Dictionary training buffers blocks as samples, so it runs serially
+        // regardless of --jobs. A sample cap bounds that buffer and streams the
+        // remaining blocks against the trained dictionary.
+        if dict_sample_blocks > 0 {
+            Writer::create_with_training_window(&output, codec, block_size, dict_sample_blocks)
+                .with_context(|| format!("creating output file {:?}", output))?
+        } else {
+            Writer::create_with_training(&output, codec, block_size)
+                .with_context(|| format!("creating output file {:?}", output))?
+        }
+    } else if jobs > 1 {
+        Writer::create_parallel(&output, codec, block_size, jobs)
+            .with_context(|| format!("creating output file {:?}", output))?
+    } else {
+        Writer::create(&output, codec, block_size)
+            .with_context(|| format!("creating output file {:?}", output))?
+    };
 
     let bytes_read: u64;
     let t0 = Instant::now();
@@ -173,6 +240,8 @@ fn run_compress(
         codec_display
     );
     eprintln!("  block size  : {}", human_bytes(block_size as u64));
+    eprintln!("  jobs        : {}", jobs);
+    eprintln!("  train-dict  : {}", train_dict);
     eprintln!("  blocks      : {}", block_count);
     eprintln!("  raw size    : {}", human_bytes(bytes_read));
     eprintln!("  compressed  : {}", human_bytes(compressed_size));
@@ -185,7 +254,7 @@ fn run_compress(
     Ok(())
 }
 
-fn run_decompress(input: PathBuf, output: PathBuf) -> anyhow::Result<()> {
+fn run_decompress(input: PathBuf, output: PathBuf, strict: bool) -> anyhow::Result<()> {
     // Read just the header codec_id first to pick the right codec
     let codec_id = {
         use std::io::Read;
@@ -197,7 +266,11 @@ fn run_decompress(input: PathBuf, output: PathBuf) -> anyhow::Result<()> {
     };
 
     let codec = codec_by_id(codec_id)?;
-    let mut reader = Reader::open(&input, codec)?;
+    let reader = if strict {
+        Reader::open_verified(&input, codec)?
+    } else {
+        Reader::open(&input, codec)?
+    };
 
     let is_stdout = output.to_str() == Some("-");
     let mut dst: Box<dyn Write> = if is_stdout {
@@ -256,6 +329,15 @@ fn run_inspect(file: PathBuf, show_blocks: bool) -> anyhow::Result<()> {
     println!("  file on disk   : {}", human_bytes(file_size));
     println!("  ratio          : {:.2}x", reader.ratio());
     println!("  flags          : 0x{:016x}", reader.header.flags);
+    if reader.header.has_flag(ancf_core::format::FLAG_HAS_DICT) {
+        println!(
+            "  dictionary     : {} @ offset {}",
+            human_bytes(reader.header.dict_len as u64),
+            reader.header.dict_offset
+        );
+    } else {
+        println!("  dictionary     : none");
+    }
 
     if show_blocks {
         println!();
@@ -279,7 +361,12 @@ fn run_inspect(file: PathBuf, show_blocks: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn run_read_block(file: PathBuf, index: u64, output: Option<PathBuf>) -> anyhow::Result<()> {
+fn run_read_block(
+    file: PathBuf,
+    index: u64,
+    output: Option<PathBuf>,
+    strict: bool,
+) -> anyhow::Result<()> {
     let codec_id = {
         use std::io::Read;
         use ancf_core::format::{Ancf1Header, HEADER_SIZE};
@@ -289,7 +376,11 @@ fn run_read_block(file: PathBuf, index: u64, output: Option<PathBuf>) -> anyhow:
         Ancf1Header::from_bytes(&buf)?.codec_id
     };
     let codec = codec_by_id(codec_id)?;
-    let mut reader = Reader::open(&file, codec)?;
+    let reader = if strict {
+        Reader::open_verified(&file, codec)?
+    } else {
+        Reader::open(&file, codec)?
+    };
 
     eprintln!(
         "seeking to block {} (offset {} bytes from file start)...",
@@ -344,7 +435,67 @@ fn run_read_block(file: PathBuf, index: u64, output: Option<PathBuf>) -> anyhow:
     Ok(())
 }
 
-fn run_bench(file: PathBuf, count: u64, seed: u64) -> anyhow::Result<()> {
+fn run_verify(file: PathBuf) -> anyhow::Result<()> {
+    let codec_id = {
+        use std::io::Read;
+        use ancf_core::format::{Ancf1Header, HEADER_SIZE};
+        let mut f = File::open(&file)?;
+        let mut buf = [0u8; HEADER_SIZE as usize];
+        f.read_exact(&mut buf)?;
+        Ancf1Header::from_bytes(&buf)?.codec_id
+    };
+    let codec = codec_by_id(codec_id)?;
+    let reader = Reader::open(&file, codec)?;
+    let report = reader.verify()?;
+
+    println!("=== ANCF1 Verify: {:?} ===", file);
+    println!("  blocks        : {}", report.block_count);
+    println!(
+        "  index/footer  : {}",
+        if report.count_consistent { "consistent" } else { "INCONSISTENT" }
+    );
+    println!("  corrupt       : {}", report.corrupt_blocks.len());
+    println!("  unreadable    : {}", report.unreadable_blocks.len());
+    if !report.corrupt_blocks.is_empty() {
+        println!("  corrupt blocks    : {:?}", report.corrupt_blocks);
+    }
+    if !report.unreadable_blocks.is_empty() {
+        println!("  unreadable blocks : {:?}", report.unreadable_blocks);
+    }
+
+    if report.is_intact() {
+        println!("  result        : OK");
+        Ok(())
+    } else {
+        anyhow::bail!("file failed verification; run `ancf repair` to recover the intact prefix");
+    }
+}
+
+fn run_repair(file: PathBuf) -> anyhow::Result<()> {
+    let codec_id = {
+        use std::io::Read;
+        use ancf_core::format::{Ancf1Header, HEADER_SIZE};
+        let mut f = File::open(&file)?;
+        let mut buf = [0u8; HEADER_SIZE as usize];
+        f.read_exact(&mut buf)?;
+        Ancf1Header::from_bytes(&buf)?.codec_id
+    };
+    let codec = codec_by_id(codec_id)?;
+    let outcome = ancf_core::repair(&file, codec)?;
+
+    println!("=== ANCF1 Repair: {:?} ===", file);
+    println!("  original blocks : {}", outcome.original_blocks);
+    println!("  kept blocks     : {}", outcome.kept_blocks);
+    let dropped = outcome.original_blocks - outcome.kept_blocks;
+    if dropped == 0 {
+        println!("  result          : already intact, nothing dropped");
+    } else {
+        println!("  result          : recovered prefix, dropped {} trailing block(s)", dropped);
+    }
+    Ok(())
+}
+
+fn run_bench(file: PathBuf, count: u64, seed: u64, cache_mb: u64) -> anyhow::Result<()> {
     let codec_id = {
         use std::io::Read;
         use ancf_core::format::{Ancf1Header, HEADER_SIZE};
@@ -354,7 +505,8 @@ fn run_bench(file: PathBuf, count: u64, seed: u64) -> anyhow::Result<()> {
         Ancf1Header::from_bytes(&buf)?.codec_id
     };
     let codec = codec_by_id(codec_id)?;
-    let mut reader = Reader::open(&file, codec)?;
+    let reader =
+        Reader::open(&file, codec)?.with_cache((cache_mb * 1024 * 1024) as usize);
     let block_count = reader.block_count();
 
     if block_count == 0 {
@@ -413,6 +565,20 @@ fn run_bench(file: PathBuf, count: u64, seed: u64) -> anyhow::Result<()> {
     println!("    p99  : {} µs", p99);
     println!("    max  : {} µs", max);
 
+    if let Some((hits, misses)) = reader.cache_stats() {
+        let total = hits + misses;
+        let rate = if total > 0 {
+            hits as f64 / total as f64 * 100.0
+        } else {
+            0.0
+        };
+        println!("  cache:");
+        println!("    budget   : {}", human_bytes(cache_mb * 1024 * 1024));
+        println!("    hits     : {}", hits);
+        println!("    misses   : {}", misses);
+        println!("    hit rate : {:.1}%", rate);
+    }
+
     Ok(())
 }
 
@@ -427,14 +593,38 @@ fn main() -> anyhow::Result<()> {
             codec,
             zstd_level,
             block_size,
-        } => run_compress(input, output, &codec, zstd_level, block_size),
-        Commands::Decompress { input, output } => run_decompress(input, output),
+            jobs,
+            train_dict,
+            dict_sample_blocks,
+        } => run_compress(
+            input,
+            output,
+            &codec,
+            zstd_level,
+            block_size,
+            jobs,
+            train_dict,
+            dict_sample_blocks,
+        ),
+        Commands::Decompress {
+            input,
+            output,
+            strict,
+        } => run_decompress(input, output, strict),
         Commands::Inspect { file, blocks } => run_inspect(file, blocks),
         Commands::ReadBlock {
             file,
             index,
             output,
-        } => run_read_block(file, index, output),
-        Commands::Bench { file, count, seed } => run_bench(file, count, seed),
+            strict,
+        } => run_read_block(file, index, output, strict),
+        Commands::Verify { file } => run_verify(file),
+        Commands::Repair { file } => run_repair(file),
+        Commands::Bench {
+            file,
+            count,
+            seed,
+            cache_mb,
+        } => run_bench(file, count, seed, cache_mb),
     }
 }
@@ -0,0 +1,71 @@
+use ancf_core::codec::{BlockMeta, Codec};
+use ancf_core::format::{CODEC_AUTO, CODEC_PASSTHROUGH};
+
+use crate::ZstdCodec;
+
+/// Adaptive codec with a "stored vs. compressed" decision per block.
+///
+/// Each block is compressed with the inner codec; if the result is not
+/// smaller than the raw input (the classic high-entropy case documented by
+/// `test_incompressible_data_no_size_gain`), the block is stored verbatim
+/// instead. The decision is recorded per block in
+/// [`BlockMeta::block_codec_id`] so the reader can decode each block in
+/// isolation — preserving the guarantee that an ANCF file is never larger
+/// than a passthrough archive, even for mixed inputs (e.g. an MP4 next to a
+/// text log).
+///
+/// Any inner codec can be supplied through [`AutoCodec::new`], but
+/// `codec_by_id` rebuilds this codec from the file header with a zstd inner
+/// codec, so a file reopened that way decodes only if it was written with a
+/// zstd inner codec (at any level).
+pub struct AutoCodec {
+    inner: Box<dyn Codec>,
+}
+
+impl Default for AutoCodec {
+    fn default() -> Self {
+        Self { inner: Box::new(ZstdCodec::default()) }
+    }
+}
+
+impl AutoCodec {
+    /// Wrap an inner codec with the stored-fallback decision.
+    pub fn new(inner: Box<dyn Codec>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Codec for AutoCodec {
+    fn id(&self) -> u16 {
+        CODEC_AUTO
+    }
+
+    fn name(&self) -> &'static str {
+        "auto"
+    }
+
+    fn compress_block(&self, raw: &[u8], meta: &mut BlockMeta) -> anyhow::Result<Vec<u8>> {
+        let compressed = self.inner.compress_block(raw, meta)?;
+        if compressed.len() >= raw.len() {
+            // Storing verbatim is at least as small — take the passthrough path.
+            meta.block_codec_id = Some(CODEC_PASSTHROUGH);
+            meta.sidecar.clear();
+            Ok(raw.to_vec())
+        } else {
+            meta.block_codec_id = Some(self.inner.id());
+            Ok(compressed)
+        }
+    }
+
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        meta: &BlockMeta,
+        expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        match meta.block_codec_id {
+            Some(CODEC_PASSTHROUGH) => Ok(compressed.to_vec()),
+            _ => self.inner.decompress_block(compressed, meta, expected_raw_len),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use ancf_core::codec::{BlockMeta, Codec};
+use ancf_core::format::CODEC_DELTA_INT;
+
+/// Delta + zigzag + stream-vbyte codec for columns of fixed-width integers.
+///
+/// Each block is read as an array of `N` little-endian integers of width `w`
+/// bytes. The codec stores `x_0` verbatim, encodes the successive deltas
+/// `d_i = x_i - x_{i-1}` as zigzag-mapped unsigned values, and packs those with
+/// stream-vbyte: every four values share one control byte carrying four 2-bit
+/// length codes, all control bytes first and the variable-length data after.
+/// This turns slowly-varying or monotone integer columns (timestamps, ids,
+/// offsets) into a handful of bytes per value while keeping every block
+/// independently decodable.
+///
+/// `w` and `N` are written into the per-block [`BlockMeta`] sidecar, so the file
+/// carries [`ancf_core::format::FLAG_PER_BLOCK_META`]. Blocks whose length
+/// isn't a multiple of `w` keep the trailing `< w` bytes verbatim.
+///
+/// Best for: integer columns — timestamps, monotonically increasing ids, row
+/// offsets, dictionary codes.
+pub struct DeltaIntCodec {
+    /// Integer width in bytes. Stream-vbyte emits 1–4 bytes per value, so the
+    /// width is limited to 1–4.
+    width: usize,
+}
+
+impl Default for DeltaIntCodec {
+    fn default() -> Self {
+        // 32-bit integers are the classic stream-vbyte target.
+        Self { width: 4 }
+    }
+}
+
+impl DeltaIntCodec {
+    /// Construct a codec for `width`-byte integers (1–4).
+    pub fn new(width: usize) -> anyhow::Result<Self> {
+        if !(1..=4).contains(&width) {
+            anyhow::bail!("DeltaIntCodec width must be 1..=4, got {}", width);
+        }
+        Ok(Self { width })
+    }
+
+    /// Bit mask for the codec's integer width (e.g. 0xFFFF_FFFF for w=4).
+    fn mask(&self) -> u64 {
+        let bits = self.width * 8;
+        if bits >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << bits) - 1
+        }
+    }
+}
+
+/// Read `w` little-endian bytes into a u64.
+fn read_le(bytes: &[u8], w: usize) -> u64 {
+    let mut v = 0u64;
+    for (i, &b) in bytes[..w].iter().enumerate() {
+        v |= (b as u64) << (8 * i);
+    }
+    v
+}
+
+/// Append the low `w` little-endian bytes of `v` to `out`.
+fn write_le(out: &mut Vec<u8>, v: u64, w: usize) {
+    for i in 0..w {
+        out.push((v >> (8 * i)) as u8);
+    }
+}
+
+impl Codec for DeltaIntCodec {
+    fn id(&self) -> u16 {
+        CODEC_DELTA_INT
+    }
+
+    fn name(&self) -> &'static str {
+        "delta-int"
+    }
+
+    fn compress_block(&self, raw: &[u8], meta: &mut BlockMeta) -> anyhow::Result<Vec<u8>> {
+        let w = self.width;
+        let n = raw.len() / w;
+        let tail = &raw[n * w..];
+
+        // Sidecar: w (u8) | N (u32 LE) | tail_len (u8).
+        let mut sidecar = Vec::with_capacity(6);
+        sidecar.push(w as u8);
+        sidecar.extend_from_slice(&(n as u32).to_le_bytes());
+        sidecar.push(tail.len() as u8);
+        meta.sidecar = sidecar;
+
+        let mask = self.mask();
+        let sign_bit = 1u64 << (w * 8 - 1);
+        let num_deltas = n.saturating_sub(1);
+
+        // Control bytes up front, variable-length data after.
+        let control_len = num_deltas.div_ceil(4);
+        let mut control = vec![0u8; control_len];
+        let mut data = Vec::new();
+
+        if n >= 1 {
+            let mut prev = read_le(&raw[0..w], w);
+            write_le(&mut data, prev, w); // x_0 verbatim
+            for i in 0..num_deltas {
+                let x = read_le(&raw[(i + 1) * w..], w);
+                // Width-domain two's-complement delta, then zigzag to unsigned.
+                let d = x.wrapping_sub(prev) & mask;
+                let zz = ((d << 1) ^ (if d & sign_bit != 0 { mask } else { 0 })) & mask;
+                prev = x;
+
+                // 1–4 byte length; encode the low bytes LE.
+                let len = (((64 - zz.leading_zeros()) as usize + 7) / 8).max(1);
+                control[i / 4] |= ((len - 1) as u8) << (2 * (i % 4));
+                write_le(&mut data, zz, len);
+            }
+        }
+
+        let mut out = Vec::with_capacity(control.len() + data.len() + tail.len());
+        out.extend_from_slice(&control);
+        out.extend_from_slice(&data);
+        out.extend_from_slice(tail);
+        Ok(out)
+    }
+
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        meta: &BlockMeta,
+        _expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        if meta.sidecar.len() != 6 {
+            anyhow::bail!(
+                "delta-int block is missing its 6-byte sidecar (got {} bytes)",
+                meta.sidecar.len()
+            );
+        }
+        let w = meta.sidecar[0] as usize;
+        if !(1..=4).contains(&w) {
+            anyhow::bail!("delta-int sidecar has invalid width {}", w);
+        }
+        let n = u32::from_le_bytes(meta.sidecar[1..5].try_into()?) as usize;
+        let tail_len = meta.sidecar[5] as usize;
+
+        let mask = if w * 8 >= 64 { u64::MAX } else { (1u64 << (w * 8)) - 1 };
+        let num_deltas = n.saturating_sub(1);
+        let control_len = num_deltas.div_ceil(4);
+        if compressed.len() < control_len {
+            anyhow::bail!("delta-int block is truncated (control region)");
+        }
+        let control = &compressed[..control_len];
+        let data = &compressed[control_len..];
+
+        let mut out = Vec::with_capacity(n * w + tail_len);
+        let mut pos = 0usize;
+        if n >= 1 {
+            let prev_bytes = data
+                .get(pos..pos + w)
+                .ok_or_else(|| anyhow::anyhow!("delta-int block is truncated (x_0)"))?;
+            let mut prev = read_le(prev_bytes, w);
+            pos += w;
+            write_le(&mut out, prev, w);
+
+            for i in 0..num_deltas {
+                let len = ((control[i / 4] >> (2 * (i % 4))) & 0b11) as usize + 1;
+                let bytes = data
+                    .get(pos..pos + len)
+                    .ok_or_else(|| anyhow::anyhow!("delta-int block is truncated (delta {})", i))?;
+                let zz = read_le(bytes, len);
+                pos += len;
+                // Un-zigzag back to the width-domain two's-complement delta.
+                let d = ((zz >> 1) ^ (if zz & 1 != 0 { mask } else { 0 })) & mask;
+                prev = prev.wrapping_add(d) & mask;
+                write_le(&mut out, prev, w);
+            }
+        }
+
+        // Trailing bytes that didn't form a full integer.
+        let tail = data
+            .get(pos..pos + tail_len)
+            .ok_or_else(|| anyhow::anyhow!("delta-int block is truncated (tail)"))?;
+        out.extend_from_slice(tail);
+        Ok(out)
+    }
+}
@@ -0,0 +1,231 @@
+//! FSST — Fast Static Symbol Table string compression.
+//!
+//! FSST targets the workload the block codecs handle worst: short, highly
+//! repetitive records (log lines, database text columns) where a per-block zstd
+//! frame pays more header than it saves. It learns up to 255 byte-string
+//! *symbols* (1–8 bytes each) from the corpus, then replaces each occurrence
+//! with a one-byte code. Bytes that match no symbol are emitted as an escape
+//! code (255) followed by the literal, so any input round-trips.
+//!
+//! Unlike the [`Codec`](ancf_core::Codec) implementations, the symbol table is
+//! trained once over the whole corpus rather than per block, so it is exposed
+//! as a standalone compressor through [`Fsst::train_bulk`] rather than through
+//! the block-codec trait. It is used by the comparison harness to measure the
+//! ratio a symbol-table pass reaches on the log corpus, both on its own and as
+//! an `fsst` → `zstd` cascade.
+
+use std::collections::HashMap;
+
+/// Reserved code that introduces a single literal byte.
+const ESCAPE: u8 = 255;
+
+/// Maximum number of learned symbols (codes `0..=254`; `255` is the escape).
+const MAX_SYMBOLS: usize = 255;
+
+/// Longest symbol the table will hold, in bytes.
+const MAX_SYMBOL_LEN: usize = 8;
+
+/// Training rounds; each round grows symbols by concatenating adjacent pairs.
+const TRAIN_ROUNDS: usize = 5;
+
+/// A trained Fast Static Symbol Table.
+///
+/// Build one with [`Fsst::train_bulk`], then [`compress`](Fsst::compress) and
+/// [`decompress`](Fsst::decompress) against the same table.
+pub struct Fsst {
+    /// `symbols[code]` is the byte string that `code` expands to.
+    symbols: Vec<Vec<u8>>,
+    /// Codes whose symbols are ≥2 bytes, keyed on their first two bytes and
+    /// ordered longest-first so the greedy matcher finds the longest symbol.
+    by_two: HashMap<u16, Vec<u8>>,
+    /// Code for each single-byte symbol, or `-1` when that byte has none.
+    by_one: [i16; 256],
+}
+
+impl Fsst {
+    /// Train a symbol table over `samples` (e.g. one entry per input line).
+    ///
+    /// The table starts empty — so the first round emits every byte as a
+    /// literal — and grows over [`TRAIN_ROUNDS`] rounds by scoring each emitted
+    /// symbol and each adjacent-pair concatenation (capped at
+    /// [`MAX_SYMBOL_LEN`]) by `count × length`, keeping the top
+    /// [`MAX_SYMBOLS`].
+    pub fn train_bulk(samples: &[&[u8]]) -> Self {
+        let mut table = Fsst::empty();
+        for _ in 0..TRAIN_ROUNDS {
+            let mut counts: HashMap<Vec<u8>, u64> = HashMap::new();
+            for sample in samples {
+                let emitted = table.emit_symbols(sample);
+                for sym in &emitted {
+                    *counts.entry(sym.clone()).or_insert(0) += 1;
+                }
+                for pair in emitted.windows(2) {
+                    let mut cat = pair[0].clone();
+                    cat.extend_from_slice(&pair[1]);
+                    cat.truncate(MAX_SYMBOL_LEN);
+                    *counts.entry(cat).or_insert(0) += 1;
+                }
+            }
+            if counts.is_empty() {
+                break;
+            }
+            // Score by compression gain and keep the best symbols.
+            let mut scored: Vec<(Vec<u8>, u64)> = counts
+                .into_iter()
+                .map(|(sym, count)| {
+                    let gain = count * sym.len() as u64;
+                    (sym, gain)
+                })
+                .collect();
+            scored.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            scored.truncate(MAX_SYMBOLS);
+            table = Fsst::from_symbols(scored.into_iter().map(|(sym, _)| sym).collect());
+        }
+        table
+    }
+
+    /// An empty table: every byte compresses to an escape + literal.
+    fn empty() -> Self {
+        Self { symbols: Vec::new(), by_one: [-1; 256], by_two: HashMap::new() }
+    }
+
+    /// Build a table from a symbol list, constructing the match indexes.
+    fn from_symbols(symbols: Vec<Vec<u8>>) -> Self {
+        let mut by_one = [-1i16; 256];
+        let mut by_two: HashMap<u16, Vec<u8>> = HashMap::new();
+        for (code, sym) in symbols.iter().enumerate() {
+            match sym.len() {
+                0 => continue,
+                1 => by_one[sym[0] as usize] = code as i16,
+                _ => {
+                    let key = ((sym[0] as u16) << 8) | sym[1] as u16;
+                    by_two.entry(key).or_default().push(code as u8);
+                }
+            }
+        }
+        // Longest-first so the first candidate that matches is the longest.
+        for codes in by_two.values_mut() {
+            codes.sort_by(|&a, &b| symbols[b as usize].len().cmp(&symbols[a as usize].len()));
+        }
+        Self { symbols, by_one, by_two }
+    }
+
+    /// Find the longest symbol matching `input` at its start, returning
+    /// `(code, length)`, or `None` when only the escape path applies.
+    fn longest_match(&self, input: &[u8]) -> Option<(u8, usize)> {
+        if input.len() >= 2 {
+            let key = ((input[0] as u16) << 8) | input[1] as u16;
+            if let Some(codes) = self.by_two.get(&key) {
+                for &code in codes {
+                    let sym = &self.symbols[code as usize];
+                    if input.len() >= sym.len() && input[..sym.len()] == sym[..] {
+                        return Some((code, sym.len()));
+                    }
+                }
+            }
+        }
+        let one = self.by_one[input[0] as usize];
+        if one >= 0 {
+            Some((one as u8, 1))
+        } else {
+            None
+        }
+    }
+
+    /// Greedily compress `input` into the emitted symbol strings, used during
+    /// training to tally symbol and adjacent-pair frequencies.
+    fn emit_symbols(&self, input: &[u8]) -> Vec<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut pos = 0;
+        while pos < input.len() {
+            match self.longest_match(&input[pos..]) {
+                Some((code, len)) => {
+                    out.push(self.symbols[code as usize].clone());
+                    pos += len;
+                }
+                None => {
+                    out.push(vec![input[pos]]);
+                    pos += 1;
+                }
+            }
+        }
+        out
+    }
+
+    /// Compress `input` into a stream of codes, escaping unmatched bytes.
+    pub fn compress(&self, input: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(input.len());
+        let mut pos = 0;
+        while pos < input.len() {
+            match self.longest_match(&input[pos..]) {
+                Some((code, len)) => {
+                    out.push(code);
+                    pos += len;
+                }
+                None => {
+                    out.push(ESCAPE);
+                    out.push(input[pos]);
+                    pos += 1;
+                }
+            }
+        }
+        out
+    }
+
+    /// Expand a code stream produced by [`compress`](Fsst::compress) back into
+    /// the original bytes via a flat table lookup.
+    pub fn decompress(&self, input: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(input.len() * 2);
+        let mut pos = 0;
+        while pos < input.len() {
+            let code = input[pos];
+            pos += 1;
+            if code == ESCAPE {
+                if pos < input.len() {
+                    out.push(input[pos]);
+                    pos += 1;
+                }
+            } else {
+                out.extend_from_slice(&self.symbols[code as usize]);
+            }
+        }
+        out
+    }
+
+    /// Number of learned symbols (excluding the escape code).
+    pub fn symbol_count(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Serialize the symbol table so a code stream can be decoded standalone:
+    /// `symbol_count:u16` followed by each symbol as `len:u8` + bytes.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&(self.symbols.len() as u16).to_le_bytes());
+        for sym in &self.symbols {
+            out.push(sym.len() as u8);
+            out.extend_from_slice(sym);
+        }
+        out
+    }
+
+    /// Rebuild a table from [`serialize`](Fsst::serialize) output, returning the
+    /// table and the number of bytes consumed from `buf`.
+    pub fn deserialize(buf: &[u8]) -> anyhow::Result<(Self, usize)> {
+        if buf.len() < 2 {
+            anyhow::bail!("fsst table truncated: missing symbol count");
+        }
+        let count = u16::from_le_bytes([buf[0], buf[1]]) as usize;
+        let mut pos = 2;
+        let mut symbols = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = *buf.get(pos).ok_or_else(|| anyhow::anyhow!("fsst table truncated"))? as usize;
+            pos += 1;
+            let end = pos + len;
+            let sym = buf.get(pos..end).ok_or_else(|| anyhow::anyhow!("fsst table truncated"))?;
+            symbols.push(sym.to_vec());
+            pos = end;
+        }
+        Ok((Fsst::from_symbols(symbols), pos))
+    }
+}
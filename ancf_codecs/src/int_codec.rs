@@ -0,0 +1,181 @@
+use ancf_core::codec::{BlockMeta, Codec};
+use ancf_core::format::CODEC_INT;
+
+/// Delta + zigzag + StreamVByte codec for columns of 64-bit integers.
+///
+/// Where [`crate::DeltaIntCodec`] targets narrow 1–4 byte columns, this codec is
+/// aimed at full-width `u64` data — timestamps, ids, sorted keys, point-cloud
+/// coordinates — where generic LZ4/Zstd leave a lot on the table. Each block is
+/// read as an array of little-endian `u64` values. The first value is stored
+/// verbatim; successive values are delta-coded (`v_i - v_{i-1}`), zigzag-mapped
+/// to unsigned, and packed with StreamVByte: every value gets a 2-bit control
+/// code selecting a `{1, 2, 4, 8}`-byte little-endian field, four codes per
+/// control byte, all control bytes first and the variable-length data after.
+///
+/// The value count and element width are written into the per-block
+/// [`BlockMeta`] sidecar, so the file carries
+/// [`ancf_core::format::FLAG_PER_BLOCK_META`]. A block whose length isn't
+/// a multiple of 8 keeps the trailing `< 8` bytes verbatim, and an empty block
+/// encodes to empty — so every block stays independently decodable.
+///
+/// Best for: monotone or slowly-varying 64-bit integer columns, where this
+/// typically beats LZ4 by several× on the same data.
+pub struct IntCodec {
+    /// Integer width in bytes. Fixed at 8 (`u64`); stored in the sidecar so the
+    /// decoder never has to guess.
+    width: usize,
+}
+
+impl Default for IntCodec {
+    fn default() -> Self {
+        Self { width: 8 }
+    }
+}
+
+/// Read `w` little-endian bytes into a u64.
+fn read_le(bytes: &[u8], w: usize) -> u64 {
+    let mut v = 0u64;
+    for (i, &b) in bytes[..w].iter().enumerate() {
+        v |= (b as u64) << (8 * i);
+    }
+    v
+}
+
+/// Append the low `w` little-endian bytes of `v` to `out`.
+fn write_le(out: &mut Vec<u8>, v: u64, w: usize) {
+    for i in 0..w {
+        out.push((v >> (8 * i)) as u8);
+    }
+}
+
+/// StreamVByte control code (0..=3) for a zigzag value: the smallest of
+/// `{1, 2, 4, 8}` bytes that holds it.
+fn code_for(zz: u64) -> u8 {
+    let needed = (((64 - zz.leading_zeros()) as usize) + 7) / 8;
+    match needed {
+        0 | 1 => 0,
+        2 => 1,
+        3 | 4 => 2,
+        _ => 3,
+    }
+}
+
+/// Byte length selected by a 2-bit control code.
+fn len_for(code: u8) -> usize {
+    match code & 0b11 {
+        0 => 1,
+        1 => 2,
+        2 => 4,
+        _ => 8,
+    }
+}
+
+impl Codec for IntCodec {
+    fn id(&self) -> u16 {
+        CODEC_INT
+    }
+
+    fn name(&self) -> &'static str {
+        "int"
+    }
+
+    fn compress_block(&self, raw: &[u8], meta: &mut BlockMeta) -> anyhow::Result<Vec<u8>> {
+        let w = self.width;
+        let n = raw.len() / w;
+        let tail = &raw[n * w..];
+
+        // Sidecar: w (u8) | N (u32 LE) | tail_len (u8).
+        let mut sidecar = Vec::with_capacity(6);
+        sidecar.push(w as u8);
+        sidecar.extend_from_slice(&(n as u32).to_le_bytes());
+        sidecar.push(tail.len() as u8);
+        meta.sidecar = sidecar;
+
+        let num_deltas = n.saturating_sub(1);
+        let control_len = num_deltas.div_ceil(4);
+        let mut control = vec![0u8; control_len];
+        let mut data = Vec::new();
+
+        if n >= 1 {
+            let mut prev = read_le(&raw[0..w], w);
+            write_le(&mut data, prev, w); // v_0 verbatim
+            for i in 0..num_deltas {
+                let x = read_le(&raw[(i + 1) * w..], w);
+                // Two's-complement delta, then zigzag to unsigned.
+                let d = x.wrapping_sub(prev);
+                let zz = (d << 1) ^ ((d as i64 >> 63) as u64);
+                prev = x;
+
+                let code = code_for(zz);
+                control[i / 4] |= code << (2 * (i % 4));
+                write_le(&mut data, zz, len_for(code));
+            }
+        }
+
+        let mut out = Vec::with_capacity(control.len() + data.len() + tail.len());
+        out.extend_from_slice(&control);
+        out.extend_from_slice(&data);
+        out.extend_from_slice(tail);
+        Ok(out)
+    }
+
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        meta: &BlockMeta,
+        _expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        if meta.sidecar.len() != 6 {
+            anyhow::bail!(
+                "int block is missing its 6-byte sidecar (got {} bytes)",
+                meta.sidecar.len()
+            );
+        }
+        let w = meta.sidecar[0] as usize;
+        if w != 8 {
+            anyhow::bail!("int sidecar has invalid width {} (expected 8)", w);
+        }
+        let n = u32::from_le_bytes(meta.sidecar[1..5].try_into()?) as usize;
+        let tail_len = meta.sidecar[5] as usize;
+
+        let num_deltas = n.saturating_sub(1);
+        let control_len = num_deltas.div_ceil(4);
+        if compressed.len() < control_len {
+            anyhow::bail!("int block is truncated (control region)");
+        }
+        let control = &compressed[..control_len];
+        let data = &compressed[control_len..];
+
+        let mut out = Vec::with_capacity(n * w + tail_len);
+        let mut pos = 0usize;
+        if n >= 1 {
+            let prev_bytes = data
+                .get(pos..pos + w)
+                .ok_or_else(|| anyhow::anyhow!("int block is truncated (v_0)"))?;
+            let mut prev = read_le(prev_bytes, w);
+            pos += w;
+            write_le(&mut out, prev, w);
+
+            for i in 0..num_deltas {
+                let code = (control[i / 4] >> (2 * (i % 4))) & 0b11;
+                let len = len_for(code);
+                let bytes = data
+                    .get(pos..pos + len)
+                    .ok_or_else(|| anyhow::anyhow!("int block is truncated (delta {})", i))?;
+                let zz = read_le(bytes, len);
+                pos += len;
+                // Un-zigzag back to the two's-complement delta and prefix-sum.
+                let d = (zz >> 1) ^ (zz & 1).wrapping_neg();
+                prev = prev.wrapping_add(d);
+                write_le(&mut out, prev, w);
+            }
+        }
+
+        // Trailing bytes that didn't form a full integer.
+        let tail = data
+            .get(pos..pos + tail_len)
+            .ok_or_else(|| anyhow::anyhow!("int block is truncated (tail)"))?;
+        out.extend_from_slice(tail);
+        Ok(out)
+    }
+}
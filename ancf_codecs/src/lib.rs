@@ -1,13 +1,32 @@
+mod auto_codec;
+mod bzip2_codec;
+mod delta_int_codec;
+mod fsst;
+mod int_codec;
 mod lz4_codec;
 mod passthrough;
+pub mod seekable;
+mod snap_codec;
+mod xz_codec;
 mod zstd_codec;
 
+pub use auto_codec::AutoCodec;
+pub use bzip2_codec::Bzip2Codec;
+pub use delta_int_codec::DeltaIntCodec;
+pub use fsst::Fsst;
+pub use int_codec::IntCodec;
 pub use lz4_codec::Lz4Codec;
 pub use passthrough::PassThroughCodec;
+pub use seekable::{FrameEntry, SeekableReader, SeekableWriter};
+pub use snap_codec::SnapCodec;
+pub use xz_codec::XzCodec;
 pub use zstd_codec::ZstdCodec;
 
 use ancf_core::Codec;
-use ancf_core::format::{CODEC_LZ4, CODEC_PASSTHROUGH, CODEC_ZSTD};
+use ancf_core::format::{
+    CODEC_AUTO, CODEC_BZIP2, CODEC_DELTA_INT, CODEC_INT, CODEC_LZ4, CODEC_PASSTHROUGH, CODEC_SNAP,
+    CODEC_XZ, CODEC_ZSTD,
+};
 use std::sync::Arc;
 
 /// Resolve a codec from its on-disk `codec_id`.
@@ -18,7 +37,16 @@ pub fn codec_by_id(id: u16) -> anyhow::Result<Arc<dyn Codec>> {
     match id {
         CODEC_PASSTHROUGH => Ok(Arc::new(PassThroughCodec)),
         CODEC_ZSTD => Ok(Arc::new(ZstdCodec::default())),
-        CODEC_LZ4 => Ok(Arc::new(Lz4Codec)),
-        _ => anyhow::bail!("unknown codec id {}; POC supports 0 (passthrough), 1 (zstd), 2 (lz4)", id),
+        CODEC_LZ4 => Ok(Arc::new(Lz4Codec::default())),
+        CODEC_DELTA_INT => Ok(Arc::new(DeltaIntCodec::default())),
+        CODEC_INT => Ok(Arc::new(IntCodec::default())),
+        CODEC_SNAP => Ok(Arc::new(SnapCodec)),
+        CODEC_AUTO => Ok(Arc::new(AutoCodec::default())),
+        CODEC_XZ => Ok(Arc::new(XzCodec::default())),
+        CODEC_BZIP2 => Ok(Arc::new(Bzip2Codec::default())),
+        _ => anyhow::bail!(
+            "unknown codec id {}; POC supports 0 (passthrough), 1 (zstd), 2 (lz4), 3 (delta-int), 7 (snappy), 8 (auto), 9 (xz), 10 (bzip2), 11 (int)",
+            id
+        ),
     }
 }
@@ -1,6 +1,14 @@
+use std::sync::Arc;
+
 use ancf_core::codec::{BlockMeta, Codec};
 use ancf_core::format::CODEC_LZ4;
-use lz4_flex::{compress_prepend_size, decompress_size_prepended};
+use lz4_flex::block::{
+    compress, compress_into_with_dict, decompress, decompress_into_with_dict,
+    get_maximum_output_size,
+};
+
+/// Window of representative bytes used as an LZ4 external dictionary.
+const LZ4_DICT_WINDOW: usize = 64 * 1024;
 
 /// LZ4 block codec.
 ///
@@ -8,8 +16,25 @@ use lz4_flex::{compress_prepend_size, decompress_size_prepended};
 /// modern hardware. Best for NVMe/local workloads where I/O latency is low
 /// and decode speed matters more than size reduction.
 ///
+/// An optional shared dictionary (see [`Lz4Codec::with_dict`]) lets small
+/// blocks reference patterns from a representative window via LZ4's
+/// external-dictionary path, recovering ratio lost to per-block isolation. The
+/// dictionary is static and stored once in the file, so random access still
+/// decodes exactly one block.
+///
 /// Best for: hot data, low-latency random access workloads.
-pub struct Lz4Codec;
+#[derive(Default)]
+pub struct Lz4Codec {
+    /// Shared external dictionary, referenced by every block when present.
+    dict: Option<Arc<Vec<u8>>>,
+}
+
+impl Lz4Codec {
+    /// Construct a dictionary-backed LZ4 codec.
+    pub fn with_dict(dict: Arc<Vec<u8>>) -> Self {
+        Self { dict: Some(dict) }
+    }
+}
 
 impl Codec for Lz4Codec {
     fn id(&self) -> u16 {
@@ -21,12 +46,62 @@ impl Codec for Lz4Codec {
     }
 
     fn compress_block(&self, raw: &[u8], _meta: &mut BlockMeta) -> anyhow::Result<Vec<u8>> {
-        Ok(compress_prepend_size(raw))
+        // No size prefix: the Reader knows the raw length from the block index
+        // and passes it to `decompress_block`, so the 4-byte prefix is redundant.
+        match &self.dict {
+            Some(dict) => {
+                let mut out = vec![0u8; get_maximum_output_size(raw.len())];
+                let n = compress_into_with_dict(raw, &mut out, dict)
+                    .map_err(|e| anyhow::anyhow!("lz4 compress error: {}", e))?;
+                out.truncate(n);
+                Ok(out)
+            }
+            None => Ok(compress(raw)),
+        }
+    }
+
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        _meta: &BlockMeta,
+        expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        match &self.dict {
+            // The Reader hands us the exact raw length, so we decode straight
+            // into a correctly-sized buffer — no size prefix, no reallocation.
+            Some(dict) => {
+                let mut out = vec![0u8; expected_raw_len];
+                decompress_into_with_dict(compressed, &mut out, dict)
+                    .map_err(|e| anyhow::anyhow!("lz4 decompress error: {}", e))?;
+                Ok(out)
+            }
+            None => decompress(compressed, expected_raw_len)
+                .map_err(|e| anyhow::anyhow!("lz4 decompress error: {}", e)),
+        }
+    }
+
+    fn train_dictionary(&self, samples: &[&[u8]]) -> anyhow::Result<Option<Vec<u8>>> {
+        if samples.is_empty() {
+            return Ok(None);
+        }
+        // LZ4's external dictionary is a raw window of representative bytes (not
+        // a trained model). Concatenate sample blocks and keep the trailing
+        // window, which holds the most context the encoder can back-reference.
+        let mut dict = Vec::new();
+        for s in samples {
+            dict.extend_from_slice(s);
+        }
+        if dict.len() > LZ4_DICT_WINDOW {
+            dict.drain(..dict.len() - LZ4_DICT_WINDOW);
+        }
+        if dict.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(dict))
+        }
     }
 
-    fn decompress_block(&self, compressed: &[u8], _meta: &BlockMeta) -> anyhow::Result<Vec<u8>> {
-        let raw = decompress_size_prepended(compressed)
-            .map_err(|e| anyhow::anyhow!("lz4 decompress error: {}", e))?;
-        Ok(raw)
+    fn with_dictionary(&self, dict: Arc<Vec<u8>>) -> anyhow::Result<Arc<dyn Codec>> {
+        Ok(Arc::new(Lz4Codec::with_dict(dict)))
     }
 }
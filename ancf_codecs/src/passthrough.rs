@@ -22,7 +22,12 @@ impl Codec for PassThroughCodec {
         Ok(raw.to_vec())
     }
 
-    fn decompress_block(&self, compressed: &[u8], _meta: &BlockMeta) -> anyhow::Result<Vec<u8>> {
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        _meta: &BlockMeta,
+        _expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
         Ok(compressed.to_vec())
     }
 }
@@ -0,0 +1,219 @@
+//! Zstd seekable-frame format: a standalone tool for interop with stock `zstd`.
+//!
+//! This is a **separate file format from ANCF1** — [`SeekableWriter`] and
+//! [`SeekableReader`] do not go through [`crate::ZstdCodec`] or the ANCF1
+//! `Reader`/`Writer` container path at all, and the ANCF1 `Reader` does not
+//! sniff for or decode seekable-zstd files. Use these types directly when you
+//! need a file that `zstd --seekable`-aware tools can read, or need to
+//! random-access a corpus one of those tools produced; reach for the ANCF1
+//! container (`ancf_core::{Reader, Writer}`) for everything else.
+//!
+//! A seekable file is a sequence of independently-decodable zstd frames
+//! followed by a skippable frame carrying a seek table:
+//!
+//! ```text
+//! [frame 0] [frame 1] ... [frame N-1]
+//! [skippable frame: magic(0x184D2A5E) + frame_size + seek table]
+//!   seek table = N × { compressed_size:u32, decompressed_size:u32 }
+//!               + footer { num_frames:u32, descriptor:u8, magic(0x8F92EAB1) }
+//! ```
+//!
+//! The benefit is bidirectional: files written by [`SeekableWriter`] decompress
+//! with the upstream `zstd` CLI, and corpora produced by `zstd --seekable` gain
+//! microsecond random access through [`SeekableReader`] without re-encoding.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Skippable-frame magic used by the zstd seekable format (in the reserved
+/// `0x184D2A50`–`0x184D2A5F` skippable range).
+const SKIPPABLE_MAGIC: u32 = 0x184D_2A5E;
+
+/// Trailing magic that identifies a seek-table footer.
+const SEEKABLE_MAGIC: u32 = 0x8F92_EAB1;
+
+/// Bytes per seek-table entry without the optional per-frame checksum.
+const ENTRY_SIZE: usize = 8;
+
+/// Bytes in the seek-table footer: `num_frames:u32 + descriptor:u8 + magic:u32`.
+const FOOTER_SIZE: usize = 9;
+
+/// One seek-table record describing a single frame.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameEntry {
+    /// Byte offset of the frame from the start of the file.
+    pub offset: u64,
+    /// Compressed size of the frame in bytes.
+    pub compressed_size: u32,
+    /// Decompressed size of the frame's content in bytes.
+    pub decompressed_size: u32,
+}
+
+/// Streaming writer producing a standards-conforming seekable-zstd file.
+///
+/// Each `block_size` chunk of input becomes one independent zstd frame; on
+/// [`finish`] the accumulated frames' sizes are serialized into the trailing
+/// skippable seek-table frame.
+pub struct SeekableWriter {
+    file: File,
+    level: i32,
+    block_size: u32,
+    pending: Vec<u8>,
+    /// `(compressed_size, decompressed_size)` per emitted frame.
+    frames: Vec<(u32, u32)>,
+}
+
+impl SeekableWriter {
+    /// Create a new seekable-zstd file at `path`.
+    pub fn create(path: impl AsRef<Path>, level: i32, block_size: u32) -> anyhow::Result<Self> {
+        Ok(Self {
+            file: File::create(path)?,
+            level,
+            block_size,
+            pending: Vec::with_capacity(block_size as usize * 2),
+            frames: Vec::new(),
+        })
+    }
+
+    /// Buffer `data`, emitting a frame each time `block_size` bytes accumulate.
+    pub fn write(&mut self, data: &[u8]) -> anyhow::Result<()> {
+        self.pending.extend_from_slice(data);
+        while self.pending.len() >= self.block_size as usize {
+            let raw: Vec<u8> = self.pending.drain(..self.block_size as usize).collect();
+            self.emit_frame(&raw)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "pure-rust"))]
+    fn emit_frame(&mut self, raw: &[u8]) -> anyhow::Result<()> {
+        let frame = zstd::bulk::compress(raw, self.level)?;
+        self.file.write_all(&frame)?;
+        self.frames.push((frame.len() as u32, raw.len() as u32));
+        Ok(())
+    }
+
+    #[cfg(feature = "pure-rust")]
+    fn emit_frame(&mut self, _raw: &[u8]) -> anyhow::Result<()> {
+        anyhow::bail!(
+            "seekable-zstd writing needs zstd compression, unavailable in pure-rust builds"
+        )
+    }
+
+    /// Flush any trailing partial frame and append the seek-table skippable
+    /// frame, returning the number of frames written.
+    pub fn finish(mut self) -> anyhow::Result<u64> {
+        if !self.pending.is_empty() {
+            let raw = std::mem::take(&mut self.pending);
+            self.emit_frame(&raw)?;
+        }
+
+        // Seek-table payload: one entry per frame, then the footer.
+        let mut table = Vec::with_capacity(self.frames.len() * ENTRY_SIZE + FOOTER_SIZE);
+        for (comp, decomp) in &self.frames {
+            table.extend_from_slice(&comp.to_le_bytes());
+            table.extend_from_slice(&decomp.to_le_bytes());
+        }
+        table.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+        table.push(0u8); // descriptor: no per-frame checksums
+        table.extend_from_slice(&SEEKABLE_MAGIC.to_le_bytes());
+
+        // Wrap the table in a skippable frame.
+        self.file.write_all(&SKIPPABLE_MAGIC.to_le_bytes())?;
+        self.file.write_all(&(table.len() as u32).to_le_bytes())?;
+        self.file.write_all(&table)?;
+        self.file.flush()?;
+
+        Ok(self.frames.len() as u64)
+    }
+}
+
+/// Random-access reader over a seekable-zstd file.
+///
+/// Parses the trailing seek table, then decodes any single frame in isolation —
+/// whether the file came from [`SeekableWriter`] or the upstream
+/// `zstd --seekable` tool.
+pub struct SeekableReader {
+    file: File,
+    entries: Vec<FrameEntry>,
+}
+
+impl SeekableReader {
+    /// Open a seekable-zstd file and parse its seek table.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let mut file = File::open(path)?;
+        let file_len = file.seek(SeekFrom::End(0))?;
+
+        // Footer sits at the very end of the skippable frame.
+        if file_len < FOOTER_SIZE as u64 {
+            anyhow::bail!("file too small to be a seekable-zstd file");
+        }
+        file.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))?;
+        let mut footer = [0u8; FOOTER_SIZE];
+        file.read_exact(&mut footer)?;
+        let num_frames = u32::from_le_bytes(footer[0..4].try_into()?) as usize;
+        let descriptor = footer[4];
+        let magic = u32::from_le_bytes(footer[5..9].try_into()?);
+        if magic != SEEKABLE_MAGIC {
+            anyhow::bail!("not a seekable-zstd file: missing seek-table magic");
+        }
+        // Bit 7 of the descriptor signals a per-frame checksum field.
+        let entry_size = if descriptor & 0x80 != 0 { ENTRY_SIZE + 4 } else { ENTRY_SIZE };
+
+        // The seek table (entries + footer) precedes nothing else; read it back.
+        let table_len = num_frames * entry_size + FOOTER_SIZE;
+        file.seek(SeekFrom::End(-(table_len as i64)))?;
+        let mut table = vec![0u8; num_frames * entry_size];
+        file.read_exact(&mut table)?;
+
+        // Frame offsets are the running sum of compressed sizes from file start.
+        let mut entries = Vec::with_capacity(num_frames);
+        let mut offset = 0u64;
+        for rec in table.chunks_exact(entry_size) {
+            let compressed_size = u32::from_le_bytes(rec[0..4].try_into()?);
+            let decompressed_size = u32::from_le_bytes(rec[4..8].try_into()?);
+            entries.push(FrameEntry { offset, compressed_size, decompressed_size });
+            offset += compressed_size as u64;
+        }
+
+        Ok(Self { file, entries })
+    }
+
+    /// Number of frames in the file.
+    pub fn frame_count(&self) -> u64 {
+        self.entries.len() as u64
+    }
+
+    /// Seek-table entries, for inspection.
+    pub fn entries(&self) -> &[FrameEntry] {
+        &self.entries
+    }
+
+    /// Decompress and return the raw bytes of frame `idx`, touching only that
+    /// frame on disk.
+    pub fn read_frame(&mut self, idx: u64) -> anyhow::Result<Vec<u8>> {
+        let entry = *self
+            .entries
+            .get(idx as usize)
+            .ok_or_else(|| anyhow::anyhow!("frame index {} out of range", idx))?;
+        self.file.seek(SeekFrom::Start(entry.offset))?;
+        let mut frame = vec![0u8; entry.compressed_size as usize];
+        self.file.read_exact(&mut frame)?;
+        decode_frame(&frame)
+    }
+}
+
+#[cfg(not(feature = "pure-rust"))]
+fn decode_frame(frame: &[u8]) -> anyhow::Result<Vec<u8>> {
+    Ok(zstd::decode_all(frame)?)
+}
+
+#[cfg(feature = "pure-rust")]
+fn decode_frame(frame: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut dec = ruzstd::StreamingDecoder::new(std::io::Cursor::new(frame))
+        .map_err(|e| anyhow::anyhow!("ruzstd decode init error: {}", e))?;
+    let mut out = Vec::new();
+    dec.read_to_end(&mut out)?;
+    Ok(out)
+}
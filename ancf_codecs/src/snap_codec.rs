@@ -0,0 +1,42 @@
+use ancf_core::codec::{BlockMeta, Codec};
+use ancf_core::format::CODEC_SNAP;
+
+/// Snappy block codec.
+///
+/// Sits between LZ4 and Zstd on the speed/ratio spectrum: far faster than
+/// zstd while holding a better ratio floor than LZ4's worst case. The raw
+/// Snappy block format prepends a varint of the uncompressed length, so
+/// `decompress_block` can size its output buffer exactly from the payload
+/// alone.
+///
+/// Best for: throughput-bound pipelines that still want some size reduction.
+pub struct SnapCodec;
+
+impl Codec for SnapCodec {
+    fn id(&self) -> u16 {
+        CODEC_SNAP
+    }
+
+    fn name(&self) -> &'static str {
+        "snappy"
+    }
+
+    fn compress_block(&self, raw: &[u8], _meta: &mut BlockMeta) -> anyhow::Result<Vec<u8>> {
+        let compressed = snap::raw::Encoder::new()
+            .compress_vec(raw)
+            .map_err(|e| anyhow::anyhow!("snappy compress error: {}", e))?;
+        Ok(compressed)
+    }
+
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        _meta: &BlockMeta,
+        _expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        let raw = snap::raw::Decoder::new()
+            .decompress_vec(compressed)
+            .map_err(|e| anyhow::anyhow!("snappy decompress error: {}", e))?;
+        Ok(raw)
+    }
+}
@@ -0,0 +1,50 @@
+use ancf_core::codec::{BlockMeta, Codec};
+use ancf_core::format::CODEC_XZ;
+
+/// High-ratio LZMA2 block codec built on `lzma-rs`.
+///
+/// Where the other codecs trade ratio for speed, this gives a high-density
+/// tier for write-once / read-rarely ANCF1 archives while preserving the
+/// random-access block index. ANCF1 already frames and checksums each block,
+/// so this codec operates on raw LZMA2 streams (no XZ container / per-block
+/// CRC) to avoid double framing.
+///
+/// `lzma-rs` doesn't expose a preset/dictionary-size knob on its LZMA2 encoder,
+/// so unlike [`crate::Bzip2Codec`]'s `level` there is no configurable effort
+/// here — every block compresses with the crate's fixed default parameters.
+///
+/// Best for: cold-storage archives where maximum density is worth a slow
+/// compress.
+#[derive(Default)]
+pub struct XzCodec;
+
+impl Codec for XzCodec {
+    fn id(&self) -> u16 {
+        CODEC_XZ
+    }
+
+    fn name(&self) -> &'static str {
+        "xz"
+    }
+
+    fn compress_block(&self, raw: &[u8], _meta: &mut BlockMeta) -> anyhow::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut src = std::io::Cursor::new(raw);
+        lzma_rs::lzma2_compress(&mut src, &mut out)
+            .map_err(|e| anyhow::anyhow!("lzma2 compress error: {}", e))?;
+        Ok(out)
+    }
+
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        _meta: &BlockMeta,
+        _expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut src = std::io::Cursor::new(compressed);
+        lzma_rs::lzma2_decompress(&mut src, &mut out)
+            .map_err(|e| anyhow::anyhow!("lzma2 decompress error: {}", e))?;
+        Ok(out)
+    }
+}
@@ -1,3 +1,6 @@
+use std::io::Read;
+use std::sync::Arc;
+
 use ancf_core::codec::{BlockMeta, Codec};
 use ancf_core::format::CODEC_ZSTD;
 
@@ -7,21 +10,38 @@ use ancf_core::format::CODEC_ZSTD;
 /// (default: 3). Because each block is independent, any block can be
 /// decompressed without touching adjacent blocks.
 ///
+/// An optional shared dictionary (see [`ZstdCodec::with_dict`]) recovers the
+/// ratio lost to small blocks: every block is compressed against one trained
+/// dictionary that is stored once in the file and loaded once at open time,
+/// so random access still seeks to and decodes exactly one block.
+///
+/// With the `pure-rust` feature the codec decodes with `ruzstd` (no C
+/// toolchain, `wasm32`-friendly). `ruzstd` is decode-only, so `compress_block`
+/// returns a clear error in that build — files are still decoded identically.
+///
 /// Best for: general text, JSON, logs, mixed structured data.
 pub struct ZstdCodec {
     /// Compression level (1 = fast / larger, 22 = slow / smallest).
+    #[cfg_attr(feature = "pure-rust", allow(dead_code))]
     pub level: i32,
+    /// Shared dictionary, applied to every block when present.
+    dict: Option<Arc<Vec<u8>>>,
 }
 
 impl Default for ZstdCodec {
     fn default() -> Self {
-        Self { level: 3 }
+        Self { level: 3, dict: None }
     }
 }
 
 impl ZstdCodec {
     pub fn new(level: i32) -> Self {
-        Self { level }
+        Self { level, dict: None }
+    }
+
+    /// Construct a dictionary-backed codec at the given level.
+    pub fn with_dict(level: i32, dict: Arc<Vec<u8>>) -> Self {
+        Self { level, dict: Some(dict) }
     }
 }
 
@@ -34,17 +54,143 @@ impl Codec for ZstdCodec {
         "zstd"
     }
 
+    #[cfg(not(feature = "pure-rust"))]
     fn compress_block(&self, raw: &[u8], _meta: &mut BlockMeta) -> anyhow::Result<Vec<u8>> {
-        let compressed = zstd::bulk::compress(raw, self.level)?;
-        Ok(compressed)
+        match &self.dict {
+            Some(dict) => {
+                let mut c = zstd::bulk::Compressor::with_dictionary(self.level, dict)?;
+                Ok(c.compress(raw)?)
+            }
+            None => Ok(zstd::bulk::compress(raw, self.level)?),
+        }
+    }
+
+    #[cfg(feature = "pure-rust")]
+    fn compress_block(&self, _raw: &[u8], _meta: &mut BlockMeta) -> anyhow::Result<Vec<u8>> {
+        anyhow::bail!(
+            "zstd compression is unavailable in pure-rust builds (ruzstd is decode-only); \
+             compress with lz4 or use a C-enabled build"
+        )
+    }
+
+    #[cfg(not(feature = "pure-rust"))]
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        _meta: &BlockMeta,
+        expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        match &self.dict {
+            // Streaming decode with the dictionary so we don't need to know the
+            // output size up front; the block frame still carries its content size.
+            Some(dict) => {
+                let mut dec =
+                    zstd::Decoder::with_dictionary(std::io::Cursor::new(compressed), dict)?;
+                let mut out = Vec::with_capacity(expected_raw_len);
+                dec.read_to_end(&mut out)?;
+                Ok(out)
+            }
+            // We know the original block size from BlockEntry.raw_len but the
+            // zstd frame also carries its own content size, so we let zstd decode
+            // into a fresh Vec without needing to pre-size it.
+            None => Ok(zstd::decode_all(compressed)?),
+        }
+    }
+
+    #[cfg(feature = "pure-rust")]
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        _meta: &BlockMeta,
+        expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        // ruzstd decodes C-produced frames identically. Dictionary frames are
+        // not supported by the pure-rust backend yet — fail loudly rather than
+        // return garbage.
+        if self.dict.is_some() {
+            anyhow::bail!(
+                "pure-rust zstd backend cannot decode dictionary-compressed blocks; \
+                 use a C-enabled build"
+            );
+        }
+        let mut dec = ruzstd::StreamingDecoder::new(std::io::Cursor::new(compressed))
+            .map_err(|e| anyhow::anyhow!("ruzstd decode init error: {}", e))?;
+        let mut out = Vec::with_capacity(expected_raw_len);
+        dec.read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    #[cfg(not(feature = "pure-rust"))]
+    fn decompress_partial(
+        &self,
+        compressed: &[u8],
+        _meta: &BlockMeta,
+        wanted: usize,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        // The streaming decoder produces bytes on demand, so we can stop once
+        // `wanted` bytes are out instead of inflating the whole frame.
+        let mut dec: Box<dyn Read> = match &self.dict {
+            Some(dict) => {
+                Box::new(zstd::Decoder::with_dictionary(std::io::Cursor::new(compressed), dict)?)
+            }
+            None => Box::new(zstd::Decoder::new(std::io::Cursor::new(compressed))?),
+        };
+        let mut out = vec![0u8; wanted];
+        let mut filled = 0;
+        while filled < wanted {
+            let n = dec.read(&mut out[filled..])?;
+            if n == 0 {
+                break; // block shorter than `wanted`
+            }
+            filled += n;
+        }
+        out.truncate(filled);
+        Ok(Some(out))
+    }
+
+    #[cfg(feature = "pure-rust")]
+    fn decompress_partial(
+        &self,
+        compressed: &[u8],
+        _meta: &BlockMeta,
+        wanted: usize,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        if self.dict.is_some() {
+            // Dictionary frames aren't supported by the pure-rust backend; let
+            // the caller fall back to the (also-erroring) full path.
+            return Ok(None);
+        }
+        let mut dec = ruzstd::StreamingDecoder::new(std::io::Cursor::new(compressed))
+            .map_err(|e| anyhow::anyhow!("ruzstd decode init error: {}", e))?;
+        let mut out = vec![0u8; wanted];
+        let mut filled = 0;
+        while filled < wanted {
+            let n = dec.read(&mut out[filled..])?;
+            if n == 0 {
+                break;
+            }
+            filled += n;
+        }
+        out.truncate(filled);
+        Ok(Some(out))
+    }
+
+    #[cfg(not(feature = "pure-rust"))]
+    fn train_dictionary(&self, samples: &[&[u8]]) -> anyhow::Result<Option<Vec<u8>>> {
+        if samples.is_empty() {
+            return Ok(None);
+        }
+        // Size the dictionary to the corpus: ~1/100th of the samples, clamped to
+        // a sane window. Tiny corpora can't train a useful dictionary — fall back.
+        let total: usize = samples.iter().map(|s| s.len()).sum();
+        let dict_size = (total / 100).clamp(4 * 1024, 112 * 1024);
+        match zstd::dict::from_samples(samples, dict_size) {
+            Ok(dict) if !dict.is_empty() => Ok(Some(dict)),
+            _ => Ok(None),
+        }
     }
 
-    fn decompress_block(&self, compressed: &[u8], _meta: &BlockMeta) -> anyhow::Result<Vec<u8>> {
-        // We know the original block size from BlockEntry.raw_len but the
-        // zstd frame also carries its own content size, so we let zstd decode
-        // into a fresh Vec without needing to pre-size it. For production
-        // we'd pass raw_len as a hint; for the POC this is sufficient.
-        let raw = zstd::decode_all(compressed)?;
-        Ok(raw)
+    fn with_dictionary(&self, dict: Arc<Vec<u8>>) -> anyhow::Result<Arc<dyn Codec>> {
+        Ok(Arc::new(ZstdCodec::with_dict(self.level, dict)))
     }
 }
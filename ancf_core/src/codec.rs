@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 /// Per-block sidecar metadata written and read by the codec.
 ///
 /// For generic codecs (PassThrough, Zstd, Lz4) this is always empty.
@@ -7,6 +9,12 @@
 #[derive(Default, Debug, Clone)]
 pub struct BlockMeta {
     pub sidecar: Vec<u8>,
+    /// Per-block codec discriminator. Adaptive codecs set this during
+    /// `compress_block` to record which path they took; the [`crate::Writer`]
+    /// persists it in [`crate::format::BlockEntry::block_codec_id`] and the
+    /// [`crate::Reader`] restores it before `decompress_block` so the codec can
+    /// dispatch. `None` means "same as the file-level codec".
+    pub block_codec_id: Option<u16>,
 }
 
 /// Core compression abstraction.
@@ -35,5 +43,56 @@ pub trait Codec: Send + Sync {
     ///
     /// `meta` contains the sidecar written by `compress_block` for this block.
     /// For codecs with `meta.sidecar.is_empty()`, this argument can be ignored.
-    fn decompress_block(&self, compressed: &[u8], meta: &BlockMeta) -> anyhow::Result<Vec<u8>>;
+    ///
+    /// `expected_raw_len` is the block's uncompressed size, which the
+    /// [`crate::Reader`] already knows from
+    /// [`crate::format::BlockEntry::raw_len`]. Codecs may use it to preallocate
+    /// the exact output buffer, and frame formats that would otherwise embed the
+    /// size (e.g. LZ4) can omit it and decode directly into a known-size buffer.
+    fn decompress_block(
+        &self,
+        compressed: &[u8],
+        meta: &BlockMeta,
+        expected_raw_len: usize,
+    ) -> anyhow::Result<Vec<u8>>;
+
+    /// Decompress only the first `wanted` bytes of a block, stopping early when
+    /// the codec's format allows it.
+    ///
+    /// Returns `Ok(None)` for codecs that can't decode a prefix cheaply (the
+    /// default); callers then fall back to a full [`decompress_block`] and slice
+    /// the result. When `Some(buf)` is returned, `buf` holds at least `wanted`
+    /// bytes (or the whole block if it is shorter). This backs the exact
+    /// byte-range reader ([`crate::Reader::read_range`]), which often needs only
+    /// a short prefix of the last block in a range.
+    fn decompress_partial(
+        &self,
+        _compressed: &[u8],
+        _meta: &BlockMeta,
+        _wanted: usize,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    /// Train a single shared dictionary from a set of raw block samples.
+    ///
+    /// Returns `Ok(None)` for codecs that don't support dictionaries (the
+    /// default). Dictionary-capable codecs return the serialized dictionary
+    /// bytes, which the [`crate::Writer`] persists once in a dedicated file
+    /// region (see [`crate::format::FLAG_HAS_DICT`]). The dictionary keeps
+    /// blocks independent: it is static and loaded once, so random access
+    /// still touches exactly one block.
+    fn train_dictionary(&self, _samples: &[&[u8]]) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    /// Return an equivalent codec that compresses/decompresses every block
+    /// against the shared `dict`.
+    ///
+    /// The default implementation rejects the call — only dictionary-capable
+    /// codecs override it. [`crate::Reader`] invokes this once at open time
+    /// when the file's header carries [`crate::format::FLAG_HAS_DICT`].
+    fn with_dictionary(&self, _dict: Arc<Vec<u8>>) -> anyhow::Result<Arc<dyn Codec>> {
+        anyhow::bail!("codec '{}' does not support a shared dictionary", self.name())
+    }
 }
@@ -0,0 +1,115 @@
+//! Per-block authenticated encryption for the ANCF1 writer/reader pipeline.
+//!
+//! Encryption is applied after compression and before the block checksum, so
+//! it composes with any codec and preserves random access: each block carries
+//! its own 96-bit nonce and GCM tag and is decrypted in isolation, touching no
+//! other block.
+//!
+//! Key derivation is PBKDF2-HMAC-SHA256 over a caller-supplied key/passphrase
+//! and a random per-file salt; the salt, iteration count, and algorithm id are
+//! persisted in a small region written alongside the main header.
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha256;
+
+/// Size of the key-derivation region written after the header when encrypting.
+///   salt[16] + iterations:u32 + algorithm_id:u16 + reserved[2] = 24
+pub const KDF_REGION_SIZE: u64 = 24;
+
+/// Algorithm id stored in the KDF region: PBKDF2-HMAC-SHA256 → AES-256-GCM.
+pub const KDF_ALG_PBKDF2_AES256GCM: u16 = 1;
+
+/// Default PBKDF2 iteration count.
+pub const DEFAULT_PBKDF2_ITERS: u32 = 100_000;
+
+/// Length of the per-block nonce in bytes.
+pub const NONCE_LEN: usize = 12;
+
+/// Parsed/constructed key-derivation parameters for one file.
+#[derive(Debug, Clone)]
+pub struct KdfParams {
+    pub salt: [u8; 16],
+    pub iterations: u32,
+    pub algorithm_id: u16,
+}
+
+impl KdfParams {
+    /// Generate fresh parameters with a random salt and default iteration count.
+    pub fn generate() -> Self {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            salt,
+            iterations: DEFAULT_PBKDF2_ITERS,
+            algorithm_id: KDF_ALG_PBKDF2_AES256GCM,
+        }
+    }
+
+    /// Serialize to exactly `KDF_REGION_SIZE` bytes.
+    pub fn to_bytes(&self) -> [u8; KDF_REGION_SIZE as usize] {
+        let mut buf = [0u8; KDF_REGION_SIZE as usize];
+        buf[0..16].copy_from_slice(&self.salt);
+        buf[16..20].copy_from_slice(&self.iterations.to_le_bytes());
+        buf[20..22].copy_from_slice(&self.algorithm_id.to_le_bytes());
+        buf
+    }
+
+    /// Deserialize from `KDF_REGION_SIZE` bytes.
+    pub fn from_bytes(buf: &[u8; KDF_REGION_SIZE as usize]) -> anyhow::Result<Self> {
+        let mut salt = [0u8; 16];
+        salt.copy_from_slice(&buf[0..16]);
+        Ok(Self {
+            salt,
+            iterations: u32::from_le_bytes(buf[16..20].try_into()?),
+            algorithm_id: u16::from_le_bytes(buf[20..22].try_into()?),
+        })
+    }
+}
+
+/// AES-256-GCM block cipher with a derived key.
+pub struct BlockCipher {
+    cipher: Aes256Gcm,
+}
+
+impl BlockCipher {
+    /// Derive the encryption key from `key` and the KDF parameters.
+    pub fn derive(key: &[u8], params: &KdfParams) -> anyhow::Result<Self> {
+        if params.algorithm_id != KDF_ALG_PBKDF2_AES256GCM {
+            anyhow::bail!("unsupported KDF algorithm id {}", params.algorithm_id);
+        }
+        let mut derived = [0u8; 32];
+        pbkdf2_hmac::<Sha256>(key, &params.salt, params.iterations, &mut derived);
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&derived));
+        Ok(Self { cipher })
+    }
+
+    /// Encrypt `plaintext`, returning `nonce || ciphertext || tag`.
+    pub fn encrypt(&self, plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ct = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| anyhow::anyhow!("block encryption failed"))?;
+        let mut out = Vec::with_capacity(NONCE_LEN + ct.len());
+        out.extend_from_slice(nonce.as_slice());
+        out.extend_from_slice(&ct);
+        Ok(out)
+    }
+
+    /// Decrypt a `nonce || ciphertext || tag` payload.
+    ///
+    /// Returns an error on a bad key or tampered ciphertext (GCM tag mismatch).
+    pub fn decrypt(&self, payload: &[u8]) -> anyhow::Result<Vec<u8>> {
+        if payload.len() < NONCE_LEN {
+            anyhow::bail!("encrypted block too short: {} bytes", payload.len());
+        }
+        let (nonce_bytes, ct) = payload.split_at(NONCE_LEN);
+        let nonce = Nonce::from_slice(nonce_bytes);
+        self.cipher
+            .decrypt(nonce, ct)
+            .map_err(|_| anyhow::anyhow!("block decryption failed (wrong key or tampered data)"))
+    }
+}
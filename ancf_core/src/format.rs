@@ -4,15 +4,16 @@ pub const MAGIC: &[u8; 14] = b"ANCF1\n\x00\x00\x00\x00\x00\x00\x00\x00";
 
 /// Fixed size of the ANCF1 file header in bytes.
 ///   magic[14] + version:u16 + codec_id:u16 + block_size:u32
-///   + block_count:u64 + flags:u64 + reserved[18]
-///   = 14 + 2 + 2 + 4 + 8 + 8 + 18 = 56
+///   + block_count:u64 + flags:u64 + dict_offset:u64 + dict_len:u32 + reserved[6]
+///   = 14 + 2 + 2 + 4 + 8 + 8 + 8 + 4 + 6 = 56
 pub const HEADER_SIZE: u64 = 56;
 
 /// Size of each BlockEntry in the block index, in bytes.
 ///   offset:u64 + compressed_len:u32 + raw_len:u32
-///   + checksum:u64 + metadata_len:u16 + _pad[6]
-///   = 8 + 4 + 4 + 8 + 2 + 6 = 32
-pub const BLOCK_ENTRY_SIZE: u64 = 32;
+///   + checksum:u64 + metadata_len:u16 + block_codec_id:u16
+///   + raw_checksum:u64 + crc32c:u32
+///   = 8 + 4 + 4 + 8 + 2 + 2 + 8 + 4 = 40
+pub const BLOCK_ENTRY_SIZE: u64 = 40;
 
 /// Size of the index footer (single u64 offset) in bytes.
 pub const FOOTER_SIZE: u64 = 8;
@@ -29,6 +30,27 @@ pub const FLAG_HAS_CHECKSUM: u64 = 1 << 0;
 /// like FloatQuant that embed per-block min/max tables).
 pub const FLAG_PER_BLOCK_META: u64 = 1 << 1;
 
+/// The file carries a single shared codec dictionary region, located by
+/// `dict_offset`/`dict_len` in the header. Every block is compressed against
+/// this one dictionary, so random access still touches exactly one block —
+/// the dictionary is loaded once at open time.
+pub const FLAG_HAS_DICT: u64 = 1 << 2;
+
+/// Each block's payload is independently encrypted (AES-256-GCM) after
+/// compression. A key-derivation region (salt / iterations / algorithm id)
+/// sits immediately after the main header; see [`crate::crypto`].
+pub const FLAG_ENCRYPTED: u64 = 1 << 3;
+
+/// Each block index entry carries a second checksum over the raw
+/// (pre-compression) bytes, enabling verify-on-read via `Reader::open_verified`.
+pub const FLAG_HAS_RAW_CHECKSUM: u64 = 1 << 4;
+
+/// Each block index entry carries a hardware-accelerated CRC32C (Castagnoli)
+/// checksum over the compressed bytes, checked by `Reader::read_block` and
+/// [`crate::reader::Reader::verify_all`] as a distinct, cheaper integrity
+/// layer alongside the xxhash3 [`FLAG_HAS_CHECKSUM`].
+pub const FLAG_HAS_CRC32C: u64 = 1 << 5;
+
 // ── Codec IDs ──────────────────────────────────────────────────────────────
 
 pub const CODEC_PASSTHROUGH: u16 = 0;
@@ -38,6 +60,22 @@ pub const CODEC_DELTA_INT: u16 = 3;
 pub const CODEC_FLOAT_QUANT: u16 = 4;
 pub const CODEC_BITPACK: u16 = 5;
 pub const CODEC_RLE: u16 = 6;
+pub const CODEC_SNAP: u16 = 7;
+
+/// Adaptive codec: per block, picks a configured inner codec or stores the
+/// block verbatim when compression would not shrink it. The per-block choice
+/// is recorded in [`BlockEntry::block_codec_id`].
+pub const CODEC_AUTO: u16 = 8;
+
+/// High-ratio LZMA2 codec for write-once/read-rarely cold-storage archives.
+pub const CODEC_XZ: u16 = 9;
+
+/// High-ratio bzip2 (BWT) codec, a second archival tier alongside [`CODEC_XZ`].
+pub const CODEC_BZIP2: u16 = 10;
+
+/// 64-bit integer-column codec: delta + zigzag + StreamVByte with {1,2,4,8}-byte
+/// length codes, for timestamps, ids, sorted keys and point-cloud coordinates.
+pub const CODEC_INT: u16 = 11;
 
 // ── Header ─────────────────────────────────────────────────────────────────
 
@@ -50,6 +88,11 @@ pub struct Ancf1Header {
     pub block_size: u32,
     pub block_count: u64,
     pub flags: u64,
+    /// Byte offset of the shared dictionary region, or 0 when absent.
+    /// Only meaningful when [`FLAG_HAS_DICT`] is set.
+    pub dict_offset: u64,
+    /// Length of the shared dictionary region in bytes, or 0 when absent.
+    pub dict_len: u32,
 }
 
 impl Ancf1Header {
@@ -62,7 +105,9 @@ impl Ancf1Header {
         buf[18..22].copy_from_slice(&self.block_size.to_le_bytes());
         buf[22..30].copy_from_slice(&self.block_count.to_le_bytes());
         buf[30..38].copy_from_slice(&self.flags.to_le_bytes());
-        // reserved[18] stays zero
+        buf[38..46].copy_from_slice(&self.dict_offset.to_le_bytes());
+        buf[46..50].copy_from_slice(&self.dict_len.to_le_bytes());
+        // reserved[6] stays zero
         buf
     }
 
@@ -77,6 +122,8 @@ impl Ancf1Header {
             block_size: u32::from_le_bytes(buf[18..22].try_into()?),
             block_count: u64::from_le_bytes(buf[22..30].try_into()?),
             flags: u64::from_le_bytes(buf[30..38].try_into()?),
+            dict_offset: u64::from_le_bytes(buf[38..46].try_into()?),
+            dict_len: u32::from_le_bytes(buf[46..50].try_into()?),
         })
     }
 
@@ -101,6 +148,19 @@ pub struct BlockEntry {
     /// Bytes of per-block sidecar metadata written before the compressed payload.
     /// Zero for codecs that don't use per-block metadata (PassThrough, Zstd, Lz4).
     pub metadata_len: u16,
+    /// Codec id that actually encoded this block. For most codecs this equals
+    /// the file-level `codec_id`, but adaptive codecs (e.g. `AutoCodec`) record
+    /// the per-block decision here so the reader decodes each block correctly.
+    pub block_codec_id: u16,
+    /// xxhash3-64 of the raw (pre-compression) bytes. Lets a verifying reader
+    /// confirm the decoded output matches what was written, catching codec bugs
+    /// and silent corruption that the compressed-only `checksum` misses. Zero
+    /// when [`FLAG_HAS_RAW_CHECKSUM`] is not set.
+    pub raw_checksum: u64,
+    /// CRC32C (Castagnoli) of the compressed bytes, computed with a
+    /// hardware-accelerated implementation so it stays cheap relative to
+    /// decode. Zero when [`FLAG_HAS_CRC32C`] is not set.
+    pub crc32c: u32,
 }
 
 impl BlockEntry {
@@ -112,7 +172,9 @@ impl BlockEntry {
         buf[12..16].copy_from_slice(&self.raw_len.to_le_bytes());
         buf[16..24].copy_from_slice(&self.checksum.to_le_bytes());
         buf[24..26].copy_from_slice(&self.metadata_len.to_le_bytes());
-        // buf[26..32] = 6 bytes padding, stays zero
+        buf[26..28].copy_from_slice(&self.block_codec_id.to_le_bytes());
+        buf[28..36].copy_from_slice(&self.raw_checksum.to_le_bytes());
+        buf[36..40].copy_from_slice(&self.crc32c.to_le_bytes());
         buf
     }
 
@@ -124,6 +186,9 @@ impl BlockEntry {
             raw_len: u32::from_le_bytes(buf[12..16].try_into()?),
             checksum: u64::from_le_bytes(buf[16..24].try_into()?),
             metadata_len: u16::from_le_bytes(buf[24..26].try_into()?),
+            block_codec_id: u16::from_le_bytes(buf[26..28].try_into()?),
+            raw_checksum: u64::from_le_bytes(buf[28..36].try_into()?),
+            crc32c: u32::from_le_bytes(buf[36..40].try_into()?),
         })
     }
 }
@@ -1,9 +1,10 @@
 pub mod codec;
+pub mod crypto;
 pub mod format;
 pub mod reader;
 pub mod writer;
 
 pub use codec::{BlockMeta, Codec};
 pub use format::{Ancf1Header, BlockEntry, HEADER_SIZE, MAGIC};
-pub use reader::Reader;
+pub use reader::{repair, Reader, RepairOutcome, VerifyReport};
 pub use writer::Writer;
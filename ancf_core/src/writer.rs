@@ -1,14 +1,69 @@
 use std::fs::File;
 use std::io::{Seek, SeekFrom, Write};
 use std::path::Path;
+use std::sync::Arc;
 
+use crc32c::crc32c;
 use xxhash_rust::xxh3::xxh3_64;
 
 use crate::codec::{BlockMeta, Codec};
+use crate::crypto::{BlockCipher, KdfParams};
 use crate::format::{
-    Ancf1Header, BlockEntry, BLOCK_ENTRY_SIZE, FLAG_HAS_CHECKSUM, HEADER_SIZE,
+    Ancf1Header, BlockEntry, BLOCK_ENTRY_SIZE, FLAG_ENCRYPTED, FLAG_HAS_CHECKSUM, FLAG_HAS_CRC32C,
+    FLAG_HAS_DICT, FLAG_HAS_RAW_CHECKSUM, FLAG_PER_BLOCK_META, HEADER_SIZE,
 };
 
+/// Upper bound, in multiples of the worker count, on how many raw blocks the
+/// parallel writer buffers before dispatching a batch. This caps in-flight
+/// memory at roughly `threads * PARALLEL_BATCH_FACTOR * block_size` instead of
+/// holding the entire input, while still giving every worker several blocks
+/// per batch to amortize thread hand-off.
+const PARALLEL_BATCH_FACTOR: usize = 4;
+
+/// Compress `blocks` across `threads` worker threads, returning the encoded
+/// `(payload, meta)` pairs in the same order as the input.
+///
+/// Each thread owns a contiguous slice of the input and shares the codec via
+/// `Arc`; results are reassembled slice-by-slice so the output order (and thus
+/// the on-disk block sequence) is identical to serial compression.
+fn compress_blocks_parallel(
+    codec: &Arc<dyn Codec>,
+    blocks: &[Vec<u8>],
+    threads: usize,
+) -> anyhow::Result<Vec<(Vec<u8>, BlockMeta)>> {
+    if blocks.is_empty() {
+        return Ok(Vec::new());
+    }
+    let threads = threads.clamp(1, blocks.len());
+    let chunk_size = blocks.len().div_ceil(threads);
+
+    let mut encoded = Vec::with_capacity(blocks.len());
+    std::thread::scope(|scope| -> anyhow::Result<()> {
+        let mut handles = Vec::with_capacity(threads);
+        for chunk in blocks.chunks(chunk_size) {
+            let codec = Arc::clone(codec);
+            handles.push(scope.spawn(move || -> anyhow::Result<Vec<(Vec<u8>, BlockMeta)>> {
+                let mut out = Vec::with_capacity(chunk.len());
+                for raw in chunk {
+                    let mut meta = BlockMeta::default();
+                    let compressed = codec.compress_block(raw, &mut meta)?;
+                    out.push((compressed, meta));
+                }
+                Ok(out)
+            }));
+        }
+        for handle in handles {
+            let part = handle
+                .join()
+                .map_err(|_| anyhow::anyhow!("compression worker panicked"))??;
+            encoded.extend(part);
+        }
+        Ok(())
+    })?;
+
+    Ok(encoded)
+}
+
 /// Streaming writer for ANCF1 files.
 ///
 /// # Write contract
@@ -22,13 +77,64 @@ use crate::format::{
 /// ```text
 /// [HEADER: 56 bytes placeholder]
 /// [BLOCK 0] [BLOCK 1] ... [BLOCK N-1]      ← independent compressed blocks
-/// [BLOCK INDEX: 32 bytes × N]
+/// [BLOCK INDEX: 40 bytes × N]
 /// [FOOTER: 8 bytes — u64 LE offset of block index]
 /// ← seek back to 0, overwrite header with real values
 /// ```
+/// Upper bound, in multiples of the worker count, on how many raw blocks the
+/// parallel writer buffers before dispatching a batch. This caps in-flight
+/// memory at roughly `threads * PARALLEL_BATCH_FACTOR * block_size` instead of
+/// holding the entire input, while still giving every worker several blocks
+/// per batch to amortize thread hand-off.
+const PARALLEL_BATCH_FACTOR: usize = 4;
+
+/// Compress `blocks` across `threads` worker threads, returning the encoded
+/// `(payload, meta)` pairs in the same order as the input.
+///
+/// Each thread owns a contiguous slice of the input and shares the codec via
+/// `Arc`; results are reassembled slice-by-slice so the output order (and thus
+/// the on-disk block sequence) is identical to serial compression.
+fn compress_blocks_parallel(
+    codec: &Arc<dyn Codec>,
+    blocks: &[Vec<u8>],
+    threads: usize,
+) -> anyhow::Result<Vec<(Vec<u8>, BlockMeta)>> {
+    if blocks.is_empty() {
+        return Ok(Vec::new());
+    }
+    let threads = threads.clamp(1, blocks.len());
+    let chunk_size = blocks.len().div_ceil(threads);
+
+    let mut encoded = Vec::with_capacity(blocks.len());
+    std::thread::scope(|scope| -> anyhow::Result<()> {
+        let mut handles = Vec::with_capacity(threads);
+        for chunk in blocks.chunks(chunk_size) {
+            let codec = Arc::clone(codec);
+            handles.push(scope.spawn(move || -> anyhow::Result<Vec<(Vec<u8>, BlockMeta)>> {
+                let mut out = Vec::with_capacity(chunk.len());
+                for raw in chunk {
+                    let mut meta = BlockMeta::default();
+                    let compressed = codec.compress_block(raw, &mut meta)?;
+                    out.push((compressed, meta));
+                }
+                Ok(out)
+            }));
+        }
+        for handle in handles {
+            let part = handle
+                .join()
+                .map_err(|_| anyhow::anyhow!("compression worker panicked"))??;
+            encoded.extend(part);
+        }
+        Ok(())
+    })?;
+
+    Ok(encoded)
+}
+
 pub struct Writer {
     file: File,
-    codec: Box<dyn Codec>,
+    codec: Arc<dyn Codec>,
     block_size: u32,
     /// Pending raw bytes not yet flushed into a block.
     pending: Vec<u8>,
@@ -36,6 +142,33 @@ pub struct Writer {
     entries: Vec<BlockEntry>,
     /// Current write position in the file (mirrors the file cursor).
     current_offset: u64,
+    /// When `Some`, the writer is in two-pass dictionary-training mode: raw
+    /// blocks are buffered here instead of being compressed immediately, so a
+    /// shared dictionary can be trained over them before any block is flushed.
+    training: Option<Vec<Vec<u8>>>,
+    /// Dictionary-backed codec installed once training completes (in `write()`
+    /// when the sample window fills, otherwise in `finish()`); overrides
+    /// `codec` for the actual block compression.
+    dict_codec: Option<Arc<dyn Codec>>,
+    /// When `Some(n)`, train the shared dictionary once the first `n` blocks
+    /// have been buffered as samples, then stream every remaining block against
+    /// it instead of holding the whole corpus in RAM. `None` with an active
+    /// `training` buffer means "sample the entire file" (the two-pass path).
+    sample_cap: Option<usize>,
+    /// Offset/length of the shared dictionary region once written, propagated
+    /// into the header by `finish()`. Zero until a dictionary is installed.
+    dict_offset: u64,
+    dict_len: u32,
+    /// When `Some`, each block's compressed payload is encrypted before the
+    /// checksum is computed and the block is written.
+    cipher: Option<BlockCipher>,
+    /// When `Some(n)`, blocks are buffered and compressed across `n` worker
+    /// threads in batches — from [`write`] whenever `n * PARALLEL_BATCH_FACTOR`
+    /// blocks are buffered, and for the remainder in [`finish`] — then written
+    /// back strictly in input order.
+    parallel: Option<usize>,
+    /// Raw blocks buffered for parallel compression (parallel mode only).
+    buffered: Vec<Vec<u8>>,
 }
 
 impl Writer {
@@ -53,20 +186,132 @@ impl Writer {
         file.write_all(&[0u8; HEADER_SIZE as usize])?;
         Ok(Self {
             file,
-            codec,
+            codec: Arc::from(codec),
             block_size,
             pending: Vec::with_capacity(block_size as usize * 2),
             entries: Vec::new(),
             current_offset: HEADER_SIZE,
+            training: None,
+            dict_codec: None,
+            sample_cap: None,
+            dict_offset: 0,
+            dict_len: 0,
+            cipher: None,
+            parallel: None,
+            buffered: Vec::new(),
         })
     }
 
+    /// Create a writer that compresses blocks across `threads` worker threads.
+    ///
+    /// ANCF1 blocks are independent, so compression is embarrassingly parallel;
+    /// the only ordering constraint is on-disk layout, so completed blocks are
+    /// reassembled in input order before being written. `threads == 1` is
+    /// equivalent to the serial [`create`]. The codec is shared across workers
+    /// via `Arc`, so it must be `Send + Sync` (the `Codec` trait guarantees it).
+    pub fn create_parallel(
+        path: impl AsRef<Path>,
+        codec: Box<dyn Codec>,
+        block_size: u32,
+        threads: usize,
+    ) -> anyhow::Result<Self> {
+        let mut w = Self::create(path, codec, block_size)?;
+        w.parallel = Some(threads.max(1));
+        Ok(w)
+    }
+
+    /// Create a new encrypted ANCF1 file at `path`.
+    ///
+    /// A random salt is generated and the key is stretched with
+    /// PBKDF2-HMAC-SHA256; the KDF parameters are written in a small region
+    /// immediately after the header. Each block's compressed payload is then
+    /// sealed with AES-256-GCM under a fresh per-block nonce, so a reader can
+    /// seek to and decrypt any single block independently.
+    pub fn create_encrypted(
+        path: impl AsRef<Path>,
+        codec: Box<dyn Codec>,
+        block_size: u32,
+        key: &[u8],
+    ) -> anyhow::Result<Self> {
+        let mut w = Self::create(path, codec, block_size)?;
+        let params = KdfParams::generate();
+        w.cipher = Some(BlockCipher::derive(key, &params)?);
+        // KDF region sits directly after the header, before any block.
+        w.file.write_all(&params.to_bytes())?;
+        w.current_offset += crate::crypto::KDF_REGION_SIZE;
+        Ok(w)
+    }
+
+    /// Create a new ANCF1 file that trains a shared codec dictionary.
+    ///
+    /// This is a two-pass writer: every block is buffered in memory as it is
+    /// filled, then on [`finish`] the accumulated blocks are fed to the codec's
+    /// dictionary trainer ([`Codec::train_dictionary`]). The trained dictionary
+    /// is written once as a dedicated file region (located from the header) and
+    /// every block is compressed against it. If the codec does not support
+    /// dictionaries, or the corpus is too small to train one, this degrades
+    /// gracefully to the same layout as [`create`].
+    ///
+    /// Because the whole input is held in RAM until `finish`, prefer this only
+    /// for small-block workloads where the ratio win justifies the memory.
+    pub fn create_with_training(
+        path: impl AsRef<Path>,
+        codec: Box<dyn Codec>,
+        block_size: u32,
+    ) -> anyhow::Result<Self> {
+        let mut w = Self::create(path, codec, block_size)?;
+        w.training = Some(Vec::new());
+        Ok(w)
+    }
+
+    /// Create a dictionary-training writer that samples only the first
+    /// `sample_blocks` blocks.
+    ///
+    /// Like [`create_with_training`], but bounded: once `sample_blocks` blocks
+    /// have been buffered the dictionary is trained, written to its file region,
+    /// and every subsequent block is streamed against it instead of being held
+    /// in RAM. Memory stays at roughly `sample_blocks * block_size` regardless
+    /// of input size, which is what makes dictionary mode practical on large
+    /// corpora (the recurring structure that a dictionary captures — log IPs,
+    /// paths, user-agents — is already present in the first few hundred KB).
+    ///
+    /// If the file holds fewer than `sample_blocks` blocks, it behaves exactly
+    /// like [`create_with_training`] and trains over everything in `finish`.
+    pub fn create_with_training_window(
+        path: impl AsRef<Path>,
+        codec: Box<dyn Codec>,
+        block_size: u32,
+        sample_blocks: usize,
+    ) -> anyhow::Result<Self> {
+        let mut w = Self::create(path, codec, block_size)?;
+        w.training = Some(Vec::new());
+        w.sample_cap = Some(sample_blocks.max(1));
+        Ok(w)
+    }
+
     /// Buffer `data` and flush complete blocks as they fill up.
     pub fn write(&mut self, data: &[u8]) -> anyhow::Result<()> {
         self.pending.extend_from_slice(data);
         while self.pending.len() >= self.block_size as usize {
             let raw: Vec<u8> = self.pending.drain(..self.block_size as usize).collect();
-            self.flush_block(&raw)?;
+            if let Some(buf) = self.training.as_mut() {
+                buf.push(raw);
+                // Once the sample window is full, train now and stream the rest.
+                if let Some(cap) = self.sample_cap {
+                    if buf.len() >= cap {
+                        self.train_and_flush_samples()?;
+                    }
+                }
+            } else if let Some(threads) = self.parallel {
+                self.buffered.push(raw);
+                // Back-pressure: once a full batch has accumulated, compress and
+                // write it so memory stays bounded regardless of input size.
+                if self.buffered.len() >= threads * PARALLEL_BATCH_FACTOR {
+                    self.flush_parallel_batch(threads)?;
+                }
+            } else {
+                self.flush_block(&raw)?;
+            }
         }
         Ok(())
     }
@@ -74,11 +319,41 @@ impl Writer {
     /// Compress `raw` as a single block and write it to the file.
     fn flush_block(&mut self, raw: &[u8]) -> anyhow::Result<()> {
         let mut meta = BlockMeta::default();
-        let compressed = self.codec.compress_block(raw, &mut meta)?;
-        let checksum = xxh3_64(&compressed);
+        let compressed = match &self.dict_codec {
+            Some(codec) => codec.compress_block(raw, &mut meta)?,
+            None => self.codec.compress_block(raw, &mut meta)?,
+        };
+        self.write_encoded(raw.len(), xxh3_64(raw), compressed, meta)
+    }
+
+    /// Encrypt (if configured), checksum, and append an already-compressed
+    /// block, recording its index entry. Shared by the serial and parallel
+    /// paths so the on-disk layout is identical either way.
+    fn write_encoded(
+        &mut self,
+        raw_len: usize,
+        raw_checksum: u64,
+        compressed: Vec<u8>,
+        meta: BlockMeta,
+    ) -> anyhow::Result<()> {
+        // Encrypt after compression, before checksum/write. The on-disk payload
+        // is `nonce || ciphertext || tag`; the checksum covers that payload.
+        let payload = match &self.cipher {
+            Some(cipher) => cipher.encrypt(&compressed)?,
+            None => compressed,
+        };
+        let checksum = xxh3_64(&payload);
+        let crc = crc32c(&payload);
 
         let block_offset = self.current_offset;
         let metadata_len = meta.sidecar.len() as u16;
+        // The codec may have recorded a per-block decision (adaptive codecs);
+        // otherwise the block was encoded by the active file-level codec.
+        let active_codec_id = match &self.dict_codec {
+            Some(codec) => codec.id(),
+            None => self.codec.id(),
+        };
+        let block_codec_id = meta.block_codec_id.unwrap_or(active_codec_id);
 
         // Write optional per-block metadata sidecar
         if metadata_len > 0 {
@@ -87,29 +362,91 @@ impl Writer {
             self.current_offset += 2 + meta.sidecar.len() as u64;
         }
 
-        // Write compressed payload
-        self.file.write_all(&compressed)?;
-        let compressed_len = compressed.len() as u32;
+        // Write the (optionally encrypted) payload
+        self.file.write_all(&payload)?;
+        let compressed_len = payload.len() as u32;
         self.current_offset += compressed_len as u64;
 
         self.entries.push(BlockEntry {
             offset: block_offset,
             compressed_len,
-            raw_len: raw.len() as u32,
+            raw_len: raw_len as u32,
             checksum,
             metadata_len,
+            block_codec_id,
+            raw_checksum,
+            crc32c: crc,
         });
 
         Ok(())
     }
 
+    /// Compress the currently buffered batch across the worker pool and write
+    /// the results in input order. Shared by the streaming back-pressure path
+    /// in [`write`] and the final drain in [`finish`], so the on-disk byte
+    /// order is identical regardless of how the input was chunked into batches.
+    fn flush_parallel_batch(&mut self, threads: usize) -> anyhow::Result<()> {
+        if self.buffered.is_empty() {
+            return Ok(());
+        }
+        let blocks = std::mem::take(&mut self.buffered);
+        let encoded = compress_blocks_parallel(&self.codec, &blocks, threads)?;
+        for (raw, (compressed, meta)) in blocks.iter().zip(encoded.into_iter()) {
+            self.write_encoded(raw.len(), xxh3_64(raw), compressed, meta)?;
+        }
+        Ok(())
+    }
+
+    /// Train the shared dictionary over the currently buffered sample blocks,
+    /// persist it to its file region (before any block references it), install
+    /// the dictionary-backed codec, and flush the buffered samples against it.
+    ///
+    /// If the codec can't train a dictionary from the samples, the buffered
+    /// blocks are flushed dictionary-less and the writer continues without one.
+    /// Either way the `training` buffer is consumed so later blocks stream.
+    fn train_and_flush_samples(&mut self) -> anyhow::Result<()> {
+        let blocks = self.training.take().unwrap_or_default();
+        let samples: Vec<&[u8]> = blocks.iter().map(|b| b.as_slice()).collect();
+        if let Some(dict) = self.codec.train_dictionary(&samples)? {
+            self.dict_offset = self.current_offset;
+            self.dict_len = dict.len() as u32;
+            self.file.write_all(&dict)?;
+            self.current_offset += dict.len() as u64;
+            self.dict_codec = Some(self.codec.with_dictionary(Arc::new(dict))?);
+        }
+        for raw in &blocks {
+            self.flush_block(raw)?;
+        }
+        Ok(())
+    }
+
     /// Flush remaining buffered data, write the block index + footer, and seal
     /// the file by writing the final header.
     ///
     /// Returns the number of blocks written.
     pub fn finish(mut self) -> anyhow::Result<u64> {
-        // Flush any partial trailing block
-        if !self.pending.is_empty() {
+        // ── Two-pass dictionary training ────────────────────────────────────
+        // Reached when the sample window was never filled (or `sample_cap` is
+        // None): move the final partial block into the training buffer, train
+        // over what we have, persist the dictionary region before any block,
+        // then flush every buffered block compressed against it.
+        if self.training.is_some() {
+            if !self.pending.is_empty() {
+                let last = std::mem::take(&mut self.pending);
+                self.training.as_mut().unwrap().push(last);
+            }
+            self.train_and_flush_samples()?;
+        } else if let Some(threads) = self.parallel {
+            // ── Parallel compression ────────────────────────────────────────
+            // Drain the final batch (plus any partial trailing block) across the
+            // worker pool, written back strictly in input order so the layout
+            // matches the serial path.
+            if !self.pending.is_empty() {
+                self.buffered.push(std::mem::take(&mut self.pending));
+            }
+            self.flush_parallel_batch(threads)?;
+        } else if !self.pending.is_empty() {
+            // Flush any partial trailing block
             let remaining = std::mem::take(&mut self.pending);
             self.flush_block(&remaining)?;
         }
@@ -127,12 +464,24 @@ impl Writer {
 
         // ── Seek back to 0 and write the real header ────────────────────────
         let block_count = self.entries.len() as u64;
+        let mut flags = FLAG_HAS_CHECKSUM | FLAG_HAS_RAW_CHECKSUM | FLAG_HAS_CRC32C;
+        if self.entries.iter().any(|e| e.metadata_len > 0) {
+            flags |= FLAG_PER_BLOCK_META;
+        }
+        if self.dict_len > 0 {
+            flags |= FLAG_HAS_DICT;
+        }
+        if self.cipher.is_some() {
+            flags |= FLAG_ENCRYPTED;
+        }
         let header = Ancf1Header {
             version: 1,
             codec_id: self.codec.id(),
             block_size: self.block_size,
             block_count,
-            flags: FLAG_HAS_CHECKSUM,
+            flags,
+            dict_offset: self.dict_offset,
+            dict_len: self.dict_len,
         };
         self.file.seek(SeekFrom::Start(0))?;
         self.file.write_all(&header.to_bytes())?;
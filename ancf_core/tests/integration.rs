@@ -9,7 +9,9 @@
 ///  5. Assert the file position never crossed blocks 0..N-1 (seeked directly)
 use std::sync::Arc;
 
-use ancf_codecs::{Lz4Codec, PassThroughCodec, ZstdCodec};
+use ancf_codecs::{
+    AutoCodec, DeltaIntCodec, IntCodec, Lz4Codec, PassThroughCodec, SnapCodec, XzCodec, ZstdCodec,
+};
 use ancf_core::format::DEFAULT_BLOCK_SIZE;
 use ancf_core::{Reader, Writer};
 
@@ -52,7 +54,7 @@ fn test_roundtrip_passthrough() {
     assert_eq!(blocks, 5); // 4 full + 1 partial
 
     // Read all blocks sequentially and reconstruct
-    let mut r = Reader::open(&path, Arc::new(PassThroughCodec)).unwrap();
+    let r = Reader::open(&path, Arc::new(PassThroughCodec)).unwrap();
     let mut reconstructed = Vec::new();
     for i in 0..r.block_count() {
         reconstructed.extend(r.read_block(i).unwrap());
@@ -70,7 +72,7 @@ fn test_roundtrip_zstd() {
     let blocks = w.finish().unwrap();
     assert_eq!(blocks, 9);
 
-    let mut r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
     let mut reconstructed = Vec::new();
     for i in 0..r.block_count() {
         reconstructed.extend(r.read_block(i).unwrap());
@@ -92,16 +94,286 @@ fn test_roundtrip_lz4() {
     let data = compressible_bytes(3 * DEFAULT_BLOCK_SIZE as usize);
     let path = temp_path("lz4");
 
-    let mut w = Writer::create(&path, Box::new(Lz4Codec), DEFAULT_BLOCK_SIZE).unwrap();
+    let mut w = Writer::create(&path, Box::new(Lz4Codec::default()), DEFAULT_BLOCK_SIZE).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(Lz4Codec::default())).unwrap();
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data);
+}
+
+#[test]
+fn test_roundtrip_snappy() {
+    let data = compressible_bytes(5 * DEFAULT_BLOCK_SIZE as usize + 999);
+    let path = temp_path("snappy");
+
+    let mut w = Writer::create(&path, Box::new(SnapCodec), DEFAULT_BLOCK_SIZE).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(SnapCodec)).unwrap();
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data);
+    assert!(r.compressed_size() < r.raw_size());
+}
+
+/// Snappy random access: read only one interior block.
+#[test]
+fn test_random_access_snappy() {
+    let data = pseudo_random_bytes(10 * DEFAULT_BLOCK_SIZE as usize, 0xABCD);
+    let path = temp_path("snappy_random");
+
+    let mut w = Writer::create(&path, Box::new(SnapCodec), DEFAULT_BLOCK_SIZE).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(SnapCodec)).unwrap();
+    let target = 7u64;
+    let start = target as usize * DEFAULT_BLOCK_SIZE as usize;
+    let end = start + DEFAULT_BLOCK_SIZE as usize;
+    assert_eq!(r.read_block(target).unwrap().as_slice(), &data[start..end]);
+}
+
+#[test]
+fn test_roundtrip_xz() {
+    let data = compressible_bytes(6 * DEFAULT_BLOCK_SIZE as usize + 333);
+    let path = temp_path("xz");
+
+    let mut w = Writer::create(&path, Box::new(XzCodec::default()), DEFAULT_BLOCK_SIZE).unwrap();
     w.write(&data).unwrap();
     w.finish().unwrap();
 
-    let mut r = Reader::open(&path, Arc::new(Lz4Codec)).unwrap();
+    let r = Reader::open(&path, Arc::new(XzCodec::default())).unwrap();
     let mut reconstructed = Vec::new();
     for i in 0..r.block_count() {
         reconstructed.extend(r.read_block(i).unwrap());
     }
     assert_eq!(reconstructed, data);
+    assert!(r.compressed_size() < r.raw_size());
+}
+
+/// XZ random access: decode only one interior block.
+#[test]
+fn test_random_access_xz() {
+    let data = pseudo_random_bytes(8 * DEFAULT_BLOCK_SIZE as usize, 0x9090);
+    let path = temp_path("xz_random");
+
+    let mut w = Writer::create(&path, Box::new(XzCodec::default()), DEFAULT_BLOCK_SIZE).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(XzCodec::default())).unwrap();
+    let target = 5u64;
+    let start = target as usize * DEFAULT_BLOCK_SIZE as usize;
+    let end = start + DEFAULT_BLOCK_SIZE as usize;
+    assert_eq!(r.read_block(target).unwrap().as_slice(), &data[start..end]);
+}
+
+/// Delta-int round-trip over a monotone u32 column, plus random access to one
+/// interior block. The codec must shrink a slowly-increasing id/timestamp
+/// column well below its raw 4-bytes-per-value size.
+#[test]
+fn test_roundtrip_delta_int_and_random_access() {
+    // A monotonically increasing column with small, varying strides.
+    let mut values: Vec<u32> = Vec::new();
+    let mut acc: u32 = 1_600_000_000;
+    for i in 0..40_000u32 {
+        acc = acc.wrapping_add(1 + (i % 7));
+        values.push(acc);
+    }
+    let mut data = Vec::with_capacity(values.len() * 4);
+    for v in &values {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+    let path = temp_path("delta_int");
+
+    let mut w = Writer::create(&path, Box::new(DeltaIntCodec::default()), DEFAULT_BLOCK_SIZE)
+        .unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(DeltaIntCodec::default())).unwrap();
+    assert!(r.compressed_size() < r.raw_size(), "delta-int should shrink a monotone column");
+
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data, "delta-int round-trip should be byte-exact");
+
+    // Random access: a single interior block decodes on its own.
+    let target = r.block_count() / 2;
+    let start = target as usize * DEFAULT_BLOCK_SIZE as usize;
+    let end = (start + DEFAULT_BLOCK_SIZE as usize).min(data.len());
+    assert_eq!(r.read_block(target).unwrap().as_slice(), &data[start..end]);
+}
+
+/// Delta-int edge cases: an empty input and a block whose length isn't a
+/// multiple of the integer width (the trailing bytes are kept verbatim).
+#[test]
+fn test_delta_int_partial_and_empty() {
+    // 10 u32 values (40 bytes) plus 3 trailing bytes that don't form an integer.
+    let mut data = Vec::new();
+    for v in 0..10u32 {
+        data.extend_from_slice(&(v * 1000).to_le_bytes());
+    }
+    data.extend_from_slice(&[0xAA, 0xBB, 0xCC]);
+    let path = temp_path("delta_int_partial");
+
+    let mut w = Writer::create(&path, Box::new(DeltaIntCodec::default()), DEFAULT_BLOCK_SIZE)
+        .unwrap();
+    w.write(&data).unwrap();
+    let blocks = w.finish().unwrap();
+    assert_eq!(blocks, 1);
+
+    let r = Reader::open(&path, Arc::new(DeltaIntCodec::default())).unwrap();
+    assert_eq!(r.read_block(0).unwrap(), data, "tail bytes must survive round-trip");
+
+    // An empty file produces no blocks.
+    let empty_path = temp_path("delta_int_empty");
+    let w = Writer::create(&empty_path, Box::new(DeltaIntCodec::default()), DEFAULT_BLOCK_SIZE)
+        .unwrap();
+    assert_eq!(w.finish().unwrap(), 0);
+}
+
+/// The 64-bit int codec round-trips a monotone u64 column, shrinks it well
+/// below the raw size, and decodes any interior block on its own.
+#[test]
+fn test_int_codec_monotone_roundtrip_and_random_access() {
+    // A rising timestamp-like column with small, irregular gaps — the codec's
+    // sweet spot, where each delta fits in one or two StreamVByte bytes.
+    let mut values: Vec<u64> = Vec::new();
+    let mut acc: u64 = 1_700_000_000_000;
+    for i in 0..40_000u64 {
+        acc = acc.wrapping_add(1 + (i % 11));
+        values.push(acc);
+    }
+    let mut data = Vec::with_capacity(values.len() * 8);
+    for v in &values {
+        data.extend_from_slice(&v.to_le_bytes());
+    }
+    let path = temp_path("int_codec");
+
+    let mut w =
+        Writer::create(&path, Box::new(IntCodec::default()), DEFAULT_BLOCK_SIZE).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(IntCodec::default())).unwrap();
+    assert!(r.compressed_size() < r.raw_size() / 2, "int codec should shrink a monotone u64 column");
+
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data, "int codec round-trip should be byte-exact");
+
+    // Random access: a single interior block decodes on its own.
+    let target = r.block_count() / 2;
+    let start = target as usize * DEFAULT_BLOCK_SIZE as usize;
+    let end = (start + DEFAULT_BLOCK_SIZE as usize).min(data.len());
+    assert_eq!(r.read_block(target).unwrap().as_slice(), &data[start..end]);
+}
+
+/// Int-codec edge cases: negative (decreasing) deltas survive the zigzag path,
+/// a block whose length isn't a multiple of 8 keeps its tail verbatim, and an
+/// empty input produces no blocks.
+#[test]
+fn test_int_codec_partial_and_empty() {
+    // 8 descending u64 values (64 bytes) plus 5 trailing bytes.
+    let mut data = Vec::new();
+    for v in 0..8u64 {
+        data.extend_from_slice(&((1_000_000 - v * 37).to_le_bytes()));
+    }
+    data.extend_from_slice(&[0x11, 0x22, 0x33, 0x44, 0x55]);
+    let path = temp_path("int_codec_partial");
+
+    let mut w =
+        Writer::create(&path, Box::new(IntCodec::default()), DEFAULT_BLOCK_SIZE).unwrap();
+    w.write(&data).unwrap();
+    let blocks = w.finish().unwrap();
+    assert_eq!(blocks, 1);
+
+    let r = Reader::open(&path, Arc::new(IntCodec::default())).unwrap();
+    assert_eq!(r.read_block(0).unwrap(), data, "tail bytes must survive round-trip");
+
+    // An empty file produces no blocks.
+    let empty_path = temp_path("int_codec_empty");
+    let w =
+        Writer::create(&empty_path, Box::new(IntCodec::default()), DEFAULT_BLOCK_SIZE).unwrap();
+    assert_eq!(w.finish().unwrap(), 0);
+}
+
+/// The decoded-block LRU cache returns identical bytes on a hit and records
+/// hit/miss counters so repeated random access is measurable.
+#[test]
+fn test_block_cache_hits_and_eviction() {
+    let data = compressible_bytes(8 * DEFAULT_BLOCK_SIZE as usize);
+    let path = temp_path("block_cache");
+
+    let mut w = Writer::create(&path, Box::new(ZstdCodec::default()), DEFAULT_BLOCK_SIZE).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    // Budget for ~2 decoded blocks; repeatedly hammer block 0 and 1.
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default()))
+        .unwrap()
+        .with_cache(2 * DEFAULT_BLOCK_SIZE as usize);
+
+    let first = r.read_block(0).unwrap();
+    for _ in 0..5 {
+        assert_eq!(r.read_block(0).unwrap(), first, "cached block must match");
+    }
+    r.read_block(1).unwrap();
+
+    let (hits, misses) = r.cache_stats().unwrap();
+    assert_eq!(misses, 2, "blocks 0 and 1 each miss once");
+    assert_eq!(hits, 5, "the five repeats of block 0 all hit");
+
+    // Touching enough distinct blocks evicts block 0; re-reading it misses again.
+    for i in 2..8 {
+        r.read_block(i).unwrap();
+    }
+    r.read_block(0).unwrap();
+    let (_, misses_after) = r.cache_stats().unwrap();
+    assert!(misses_after > misses, "evicted block 0 should miss on re-read");
+}
+
+/// The block-count cache variant bounds the cache by number of blocks rather
+/// than bytes, evicting the least-recently-used block once the count is full.
+#[test]
+fn test_block_cache_count_eviction() {
+    let data = compressible_bytes(8 * DEFAULT_BLOCK_SIZE as usize);
+    let path = temp_path("block_cache_count");
+
+    let mut w = Writer::create(&path, Box::new(ZstdCodec::default()), DEFAULT_BLOCK_SIZE).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    // Hold at most two blocks regardless of their decoded size.
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default()))
+        .unwrap()
+        .with_cache_blocks(2);
+
+    r.read_block(0).unwrap();
+    r.read_block(1).unwrap();
+    assert_eq!(r.read_block(0).unwrap().len(), DEFAULT_BLOCK_SIZE as usize);
+    let (hits, misses) = r.cache_stats().unwrap();
+    assert_eq!(misses, 2);
+    assert_eq!(hits, 1);
+
+    // A third distinct block evicts the LRU entry (block 1), so re-reading it misses.
+    r.read_block(2).unwrap();
+    r.read_block(1).unwrap();
+    let (_, misses_after) = r.cache_stats().unwrap();
+    assert_eq!(misses_after, 4, "block 1 was evicted and misses again");
 }
 
 /// THE CORE POC TEST: read only block N without touching blocks 0..N-1.
@@ -125,7 +397,7 @@ fn test_random_access_skips_prior_blocks() {
     assert_eq!(block_count, NUM_BLOCKS as u64);
 
     // Open and read ONLY block 12
-    let mut r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
     let raw = r.read_block(TARGET_BLOCK).unwrap();
 
     // Expected bytes for block 12
@@ -142,6 +414,151 @@ fn test_random_access_skips_prior_blocks() {
     );
 }
 
+/// Trained shared dictionary: small repetitive blocks round-trip correctly and
+/// a single block can still be read without touching its neighbours.
+#[test]
+fn test_trained_dictionary_roundtrip_and_random_access() {
+    let block_size = 4 * 1024u32; // small blocks — the case the dictionary targets
+    let data = compressible_bytes(64 * block_size as usize + 321);
+    let path = temp_path("trained_dict");
+
+    let mut w =
+        Writer::create_with_training(&path, Box::new(ZstdCodec::default()), block_size).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+    assert!(r.header.has_flag(ancf_core::format::FLAG_HAS_DICT));
+
+    // Full round-trip
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data);
+
+    // Random access to a single interior block decodes against the shared dict
+    let target = 40u64;
+    let start = target as usize * block_size as usize;
+    let end = start + block_size as usize;
+    assert_eq!(r.read_block(target).unwrap().as_slice(), &data[start..end]);
+}
+
+/// The same trained-dictionary path works for LZ4 via its external-dictionary
+/// decode, round-tripping and keeping blocks independently decodable.
+#[test]
+fn test_lz4_trained_dictionary_roundtrip_and_random_access() {
+    let block_size = 4 * 1024u32; // small blocks — the case the dictionary targets
+    let data = compressible_bytes(48 * block_size as usize + 123);
+    let path = temp_path("lz4_trained_dict");
+
+    let mut w =
+        Writer::create_with_training(&path, Box::new(Lz4Codec::default()), block_size).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(Lz4Codec::default())).unwrap();
+    assert!(r.header.has_flag(ancf_core::format::FLAG_HAS_DICT));
+
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data);
+
+    let target = 30u64;
+    let start = target as usize * block_size as usize;
+    let end = start + block_size as usize;
+    assert_eq!(r.read_block(target).unwrap().as_slice(), &data[start..end]);
+}
+
+/// Encryption round-trips, rejects the wrong key, and tampering with one
+/// block's ciphertext fails that block alone.
+#[test]
+fn test_encrypted_roundtrip_and_tamper_detection() {
+    let block_size = 4 * 1024u32;
+    let data = compressible_bytes(6 * block_size as usize + 7);
+    let path = temp_path("encrypted");
+    let key = b"correct horse battery staple";
+
+    let mut w =
+        Writer::create_encrypted(&path, Box::new(ZstdCodec::default()), block_size, key).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    // Correct key round-trips exactly.
+    {
+        let r =
+            Reader::open_encrypted(&path, Arc::new(ZstdCodec::default()), key).unwrap();
+        assert!(r.header.has_flag(ancf_core::format::FLAG_ENCRYPTED));
+        let mut reconstructed = Vec::new();
+        for i in 0..r.block_count() {
+            reconstructed.extend(r.read_block(i).unwrap());
+        }
+        assert_eq!(reconstructed, data);
+    }
+
+    // Wrong key fails.
+    {
+        let r =
+            Reader::open_encrypted(&path, Arc::new(ZstdCodec::default()), b"wrong key").unwrap();
+        assert!(r.read_block(0).is_err(), "wrong key must not decrypt");
+    }
+
+    // Flip one ciphertext byte in block 2; only that block fails.
+    let tampered_offset = {
+        let r = Reader::open_encrypted(&path, Arc::new(ZstdCodec::default()), key).unwrap();
+        // Skip the 12-byte nonce to land inside the ciphertext.
+        r.entries()[2].offset + 12
+    };
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[tampered_offset as usize] ^= 0xFF;
+    std::fs::write(&path, &bytes).unwrap();
+
+    let r = Reader::open_encrypted(&path, Arc::new(ZstdCodec::default()), key).unwrap();
+    assert!(r.read_block(2).is_err(), "tampered block must fail");
+    assert!(r.read_block(0).is_ok(), "untampered block must still decode");
+    assert!(r.read_block(3).is_ok(), "untampered block must still decode");
+}
+
+/// Parallel compression must produce a byte-identical file to the serial path.
+#[test]
+fn test_parallel_writer_bit_identical() {
+    let data = compressible_bytes(40 * DEFAULT_BLOCK_SIZE as usize + 4567);
+    let serial_path = temp_path("parallel_serial");
+    let parallel_path = temp_path("parallel_parallel");
+
+    let mut w = Writer::create(&serial_path, Box::new(ZstdCodec::default()), DEFAULT_BLOCK_SIZE)
+        .unwrap();
+    w.write(&data).unwrap();
+    let serial_blocks = w.finish().unwrap();
+
+    let mut w = Writer::create_parallel(
+        &parallel_path,
+        Box::new(ZstdCodec::default()),
+        DEFAULT_BLOCK_SIZE,
+        4,
+    )
+    .unwrap();
+    w.write(&data).unwrap();
+    let parallel_blocks = w.finish().unwrap();
+
+    assert_eq!(serial_blocks, parallel_blocks);
+    assert_eq!(
+        std::fs::read(&serial_path).unwrap(),
+        std::fs::read(&parallel_path).unwrap(),
+        "parallel writer must be bit-compatible with the serial writer"
+    );
+
+    // And it decodes correctly.
+    let r = Reader::open(&parallel_path, Arc::new(ZstdCodec::default())).unwrap();
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data);
+}
+
 /// Test read_range across a block boundary.
 #[test]
 fn test_read_range_crosses_block_boundary() {
@@ -153,7 +570,7 @@ fn test_read_range_crosses_block_boundary() {
     w.write(&data).unwrap();
     w.finish().unwrap();
 
-    let mut r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
 
     // Read a range that straddles the boundary between block 1 and block 2
     let start = block_size as u64 - 100; // 100 bytes from end of block 1
@@ -164,6 +581,61 @@ fn test_read_range_crosses_block_boundary() {
     assert_eq!(result.as_slice(), &data[start as usize..start as usize + 300]);
 }
 
+/// Raw-checksum verify-on-read catches corruption that a valid compressed
+/// checksum would let slip through. We corrupt a block's payload and patch its
+/// compressed checksums (xxh3 and CRC32C) to match, so only the raw-bytes check
+/// can fire.
+#[test]
+fn test_raw_checksum_catches_silent_corruption() {
+    use xxhash_rust::xxh3::xxh3_64;
+
+    let block_size = 4 * 1024u32;
+    let data = compressible_bytes(4 * block_size as usize);
+    let path = temp_path("raw_checksum");
+
+    // PassThrough keeps payload == raw, so a flipped byte yields valid-length
+    // wrong bytes — exactly the silent corruption the raw checksum targets.
+    let mut w = Writer::create(&path, Box::new(PassThroughCodec), block_size).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    // Locate block 1 and its index entry on disk.
+    let (block_offset, comp_len, entry_checksum_pos) = {
+        let r = Reader::open(&path, Arc::new(PassThroughCodec)).unwrap();
+        let idx_offset = {
+            let mut bytes = std::fs::read(&path).unwrap();
+            let n = bytes.len();
+            let footer: [u8; 8] = bytes.split_off(n - 8).try_into().unwrap();
+            u64::from_le_bytes(footer)
+        };
+        let e = &r.entries()[1];
+        // checksum field is at byte +16 within the 40-byte entry
+        let pos = idx_offset + ancf_core::format::BLOCK_ENTRY_SIZE + 16;
+        (e.offset, e.compressed_len as usize, pos)
+    };
+
+    // Corrupt a payload byte, then recompute + rewrite both compressed checksums.
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[block_offset as usize] ^= 0xFF;
+    let new_checksum = xxh3_64(&bytes[block_offset as usize..block_offset as usize + comp_len]);
+    bytes[entry_checksum_pos as usize..entry_checksum_pos as usize + 8]
+        .copy_from_slice(&new_checksum.to_le_bytes());
+    // crc32c field is at byte +36 within the entry (20 bytes past the checksum).
+    let new_crc = crc32c::crc32c(&bytes[block_offset as usize..block_offset as usize + comp_len]);
+    let crc_pos = entry_checksum_pos as usize + 20;
+    bytes[crc_pos..crc_pos + 4].copy_from_slice(&new_crc.to_le_bytes());
+    std::fs::write(&path, &bytes).unwrap();
+
+    // Unverified read may return garbage (compressed checksum now passes).
+    // Verified read must fail loudly on the raw checksum.
+    let r = Reader::open_verified(&path, Arc::new(PassThroughCodec)).unwrap();
+    assert!(r.header.has_flag(ancf_core::format::FLAG_HAS_RAW_CHECKSUM));
+    let err = r.read_block(1).unwrap_err().to_string();
+    assert!(err.contains("raw checksum mismatch"), "got: {err}");
+    // Other blocks are unaffected.
+    assert!(r.read_block(0).is_ok());
+}
+
 /// Test that codec mismatch on open returns a clear error.
 #[test]
 fn test_codec_mismatch_error() {
@@ -175,7 +647,7 @@ fn test_codec_mismatch_error() {
     w.finish().unwrap();
 
     // Try to open with wrong codec
-    let result = Reader::open(&path, Arc::new(Lz4Codec));
+    let result = Reader::open(&path, Arc::new(Lz4Codec::default()));
     assert!(result.is_err(), "opening with wrong codec should fail");
     let err = result.err().unwrap().to_string();
     assert!(
@@ -195,12 +667,47 @@ fn test_single_partial_block() {
     let blocks = w.finish().unwrap();
     assert_eq!(blocks, 1);
 
-    let mut r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
     assert_eq!(r.block_count(), 1);
     let raw = r.read_block(0).unwrap();
     assert_eq!(raw.as_slice(), data.as_slice());
 }
 
+/// AutoCodec must never expand blocks: on incompressible input it stores each
+/// block verbatim, so every block's compressed payload is no larger than raw.
+#[test]
+fn test_auto_codec_never_expands() {
+    let data = pseudo_random_bytes(4 * DEFAULT_BLOCK_SIZE as usize, 0x5151);
+    let path = temp_path("auto_incompressible");
+
+    let mut w = Writer::create(
+        &path,
+        Box::new(AutoCodec::new(Box::new(ZstdCodec::default()))),
+        DEFAULT_BLOCK_SIZE,
+    )
+    .unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(AutoCodec::default())).unwrap();
+    for e in r.entries() {
+        assert!(
+            e.compressed_len <= e.raw_len,
+            "auto codec stored block should not expand: {} > {}",
+            e.compressed_len,
+            e.raw_len
+        );
+        assert_eq!(e.block_codec_id, ancf_core::format::CODEC_PASSTHROUGH);
+    }
+
+    // And it still round-trips exactly.
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data);
+}
+
 /// Verify Shannon floor: pseudo-random (high-entropy) data should not compress.
 #[test]
 fn test_incompressible_data_no_size_gain() {
@@ -221,3 +728,201 @@ fn test_incompressible_data_no_size_gain() {
         ratio
     );
 }
+
+/// `verify` reports a clean file as intact, flags a block whose payload was
+/// corrupted in place, and `repair` truncates a damaged tail back to the
+/// recoverable prefix.
+#[test]
+fn test_verify_and_repair() {
+    let block_size = 4 * 1024u32;
+    let data = compressible_bytes(5 * block_size as usize);
+    let path = temp_path("verify_repair");
+
+    // PassThrough keeps payload == raw, so flipping a byte is a payload corruption.
+    let mut w = Writer::create(&path, Box::new(PassThroughCodec), block_size).unwrap();
+    w.write(&data).unwrap();
+    let blocks = w.finish().unwrap();
+    assert_eq!(blocks, 5);
+
+    // A freshly written file verifies clean.
+    let r = Reader::open(&path, Arc::new(PassThroughCodec)).unwrap();
+    assert!(r.verify().unwrap().is_intact());
+
+    // Corrupt one byte of block 3's payload (checksum left stale → mismatch).
+    let mut bytes = std::fs::read(&path).unwrap();
+    let block3_offset = {
+        let r = Reader::open(&path, Arc::new(PassThroughCodec)).unwrap();
+        r.entries()[3].offset as usize
+    };
+    bytes[block3_offset] ^= 0xFF;
+    std::fs::write(&path, &bytes).unwrap();
+
+    let r = Reader::open(&path, Arc::new(PassThroughCodec)).unwrap();
+    let report = r.verify().unwrap();
+    assert!(!report.is_intact());
+    assert_eq!(report.corrupt_blocks, vec![3]);
+
+    // Repair keeps the intact prefix (blocks 0..=2) and drops block 3 onward.
+    let outcome = ancf_core::repair(&path, Arc::new(PassThroughCodec)).unwrap();
+    assert_eq!(outcome.original_blocks, 5);
+    assert_eq!(outcome.kept_blocks, 3);
+
+    // The repaired file now verifies clean and round-trips its prefix.
+    let r = Reader::open(&path, Arc::new(PassThroughCodec)).unwrap();
+    assert!(r.verify().unwrap().is_intact());
+    assert_eq!(r.block_count(), 3);
+    let mut recovered = Vec::new();
+    for i in 0..r.block_count() {
+        recovered.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(recovered.as_slice(), &data[..3 * block_size as usize]);
+}
+
+/// Windowed dictionary training samples only the first N blocks yet still
+/// trains a shared dictionary, streams the remaining blocks against it, and
+/// round-trips with working random access.
+#[test]
+fn test_windowed_dictionary_roundtrip() {
+    let block_size = 4 * 1024u32;
+    let data = compressible_bytes(64 * block_size as usize + 77);
+    let path = temp_path("windowed_dict");
+
+    let mut w = Writer::create_with_training_window(
+        &path,
+        Box::new(ZstdCodec::default()),
+        block_size,
+        8, // sample only the first 8 blocks
+    )
+    .unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+    assert!(r.header.has_flag(ancf_core::format::FLAG_HAS_DICT));
+
+    let mut reconstructed = Vec::new();
+    for i in 0..r.block_count() {
+        reconstructed.extend(r.read_block(i).unwrap());
+    }
+    assert_eq!(reconstructed, data);
+
+    // A block written well after the sample window still decodes against the dict.
+    let target = 50u64;
+    let start = target as usize * block_size as usize;
+    let end = start + block_size as usize;
+    assert_eq!(r.read_block(target).unwrap().as_slice(), &data[start..end]);
+}
+
+/// Vectored `read_blocks` returns the same bytes as per-block `read_block`,
+/// in the caller's requested order, including repeated and out-of-order indices.
+#[test]
+fn test_read_blocks_matches_single_reads() {
+    let block_size = 2 * 1024u32;
+    let data = compressible_bytes(20 * block_size as usize + 99);
+    let path = temp_path("read_blocks");
+
+    let mut w = Writer::create(&path, Box::new(ZstdCodec::default()), block_size).unwrap();
+    w.write(&data).unwrap();
+    let blocks = w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+
+    // Out-of-order, with a repeat, spanning the whole file.
+    let want = [blocks - 1, 0, 5, 5, 12, 3];
+    let batch = r.read_blocks(&want).unwrap();
+    assert_eq!(batch.len(), want.len());
+
+    let mut single = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+    for (got, &idx) in batch.iter().zip(want.iter()) {
+        assert_eq!(got, &single.read_block(idx).unwrap());
+    }
+}
+
+/// read_range decodes the exact slice even when the range ends deep inside a
+/// block (the partial-decode fast path) and matches a full-block reference.
+#[test]
+fn test_read_range_partial_prefix_matches_full() {
+    let block_size = 16 * 1024u32;
+    let data = compressible_bytes(4 * block_size as usize + 10);
+    let path = temp_path("range_partial");
+
+    let mut w = Writer::create(&path, Box::new(ZstdCodec::default()), block_size).unwrap();
+    w.write(&data).unwrap();
+    w.finish().unwrap();
+
+    let r = Reader::open(&path, Arc::new(ZstdCodec::default())).unwrap();
+
+    // A single byte ~80% through block 2 — the core-claim "byte at offset" path.
+    let start = 2 * block_size as u64 + (block_size as u64 * 4 / 5);
+    let got = r.read_range(start, 1).unwrap();
+    assert_eq!(got.as_slice(), &data[start as usize..start as usize + 1]);
+
+    // A range ending part-way through the final block.
+    let start = block_size as u64 / 2;
+    let len = 2 * block_size as u64 + 123;
+    let got = r.read_range(start, len).unwrap();
+    assert_eq!(got.as_slice(), &data[start as usize..(start + len) as usize]);
+}
+
+/// Seekable-zstd round-trip: frames written by SeekableWriter parse back through
+/// the seek table and each frame decodes independently.
+#[test]
+fn test_seekable_zstd_roundtrip() {
+    use ancf_codecs::{SeekableReader, SeekableWriter};
+
+    let block_size = 8 * 1024u32;
+    let data = compressible_bytes(6 * block_size as usize + 42);
+    let path = temp_path("seekable");
+
+    let mut w = SeekableWriter::create(&path, 3, block_size).unwrap();
+    w.write(&data).unwrap();
+    let frames = w.finish().unwrap();
+    assert_eq!(frames, 7); // 6 full + 1 partial
+
+    let mut r = SeekableReader::open(&path).unwrap();
+    assert_eq!(r.frame_count(), frames);
+
+    // Full reconstruction from independently decoded frames.
+    let mut reconstructed = Vec::new();
+    for i in 0..r.frame_count() {
+        reconstructed.extend(r.read_frame(i).unwrap());
+    }
+    assert_eq!(reconstructed, data);
+
+    // Random access to an interior frame decodes exactly its slice.
+    let target = 4u64;
+    let start = target as usize * block_size as usize;
+    let end = start + block_size as usize;
+    assert_eq!(r.read_frame(target).unwrap().as_slice(), &data[start..end]);
+}
+
+/// FSST learns a symbol table from repetitive records and round-trips every
+/// input exactly, including bytes that match no symbol (escape path).
+#[test]
+fn test_fsst_trains_and_roundtrips() {
+    use ancf_codecs::Fsst;
+
+    // Repetitive records are what FSST is for; include a never-sampled line so
+    // the escape path is exercised on bytes outside the trained vocabulary.
+    let lines: Vec<Vec<u8>> = (0..2000u32)
+        .map(|i| format!("GET /api/v1/users/{}/profile 200\n", i % 37).into_bytes())
+        .collect();
+    let refs: Vec<&[u8]> = lines.iter().map(|l| l.as_slice()).collect();
+    let table = Fsst::train_bulk(&refs);
+    assert!(table.symbol_count() > 0);
+
+    let mut raw_total = 0usize;
+    let mut packed_total = 0usize;
+    for line in &lines {
+        let packed = table.compress(line);
+        raw_total += line.len();
+        packed_total += packed.len();
+        assert_eq!(&table.decompress(&packed), line);
+    }
+    // The trained table should shrink this highly repetitive corpus overall.
+    assert!(packed_total < raw_total);
+
+    // An input with bytes absent from the corpus still round-trips verbatim.
+    let novel = b"\x00\xff\xfe binary \x01\x02 not in corpus".to_vec();
+    assert_eq!(table.decompress(&table.compress(&novel)), novel);
+}